@@ -1,8 +1,10 @@
 use std::borrow::Cow;
 use std::future::IntoFuture;
+use std::time::Duration;
 
 use crate::{Client, Requestable, Result};
 use crate::client::ApiResponse;
+use crate::{RequestPreview, ResponseMetadata, WithMetadata};
 use crate::Card;
 use crate::utils::futurize;
 
@@ -47,6 +49,18 @@ impl GetCardBuilder {
 		GetCardBuilder { client, request: GetCard::new(id.into()) }
 	}
 
+	/// The longest to wait for each request to complete, overriding the client's timeout.
+	pub fn timeout(mut self, value: Duration) -> GetCardBuilder {
+		self.client.options_mut().timeout = Some(value);
+		self
+	}
+
+	/// The API key to send with this request, overriding the client's key.
+	pub fn api_key(mut self, key: impl Into<String>) -> GetCardBuilder {
+		self.client.options_mut().api_key = Some(key.into());
+		self
+	}
+
 	/// Specific fields to fetch with the card.
 	/// 
 	/// Always includes "id" if not added.
@@ -83,10 +97,34 @@ impl GetCardBuilder {
 		let ret: ApiResponse<Card> = self.client.get(self.request).await?;
 		Ok(ret.data)
 	}
+
+	/// The URL this request would be sent to, including the encoded query parameters.
+	pub fn url(&self) -> Result<String> {
+		Ok(self.to_request()?.url)
+	}
+
+	/// The request that would be sent, with the API key redacted.
+	pub fn to_request(&self) -> Result<RequestPreview> {
+		self.client.preview(&self.request)
+	}
+
+	/// Also returns the [`ResponseMetadata`] of the response, such as its status and rate limit.
+	pub fn with_metadata(self) -> WithMetadata<GetCardBuilder> {
+		WithMetadata { builder: self }
+	}
 }
 
 futurize!(GetCardBuilder, Option<Card>);
 
+impl WithMetadata<GetCardBuilder> {
+	async fn send(self) -> Result<(Option<Card>, ResponseMetadata)> {
+		let (ret, metadata): (ApiResponse<Card>, _) = self.builder.client.get_with_metadata(self.builder.request).await?;
+		Ok((ret.data, metadata))
+	}
+}
+
+futurize!(WithMetadata<GetCardBuilder>, (Option<Card>, ResponseMetadata));
+
 impl Client {
 	/// Convenience method to make a request to the cards/{id} endpoint.
 	pub fn get_card(&self, id: impl Into<String>) -> GetCardBuilder {
@@ -137,4 +175,15 @@ mod tests {
 
 		Ok(())
 	}
+
+	#[tokio::test]
+	async fn test_card_with_metadata() -> Result<()> {
+		let client = client();
+		let (card, metadata) = client.get_card("xy1-1").with_metadata().await?;
+		assert!(card.is_some());
+		assert_eq!(metadata.status, 200);
+		assert!(client.stats().requests > 0);
+
+		Ok(())
+	}
 }
\ No newline at end of file
@@ -0,0 +1,192 @@
+use std::future::IntoFuture;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future;
+use tokio::sync::Semaphore;
+
+use crate::{BatchResult, Client, RequestPreview, ResponseMetadata, Result, WithMetadata};
+use crate::client::{dedupe_ids, id_queries};
+use crate::Card;
+use super::{SearchCardsBuilder, SharedSets};
+use crate::utils::futurize;
+
+/// A builder to fetch several cards by id in as few requests as possible.
+/// 
+/// To construct a `GetCardsBuilder`, refer to the `Client` documentation.
+#[derive(Debug, Clone)]
+pub struct GetCardsBuilder {
+	client: Client,
+	ids: Vec<String>,
+	select: Option<String>,
+	share_sets: bool,
+}
+
+impl GetCardsBuilder {
+	pub(crate) fn new(client: Client, ids: Vec<String>) -> GetCardsBuilder {
+		GetCardsBuilder { client, ids, select: None, share_sets: false }
+	}
+
+	/// The longest to wait for each request to complete, overriding the client's timeout.
+	pub fn timeout(mut self, value: Duration) -> GetCardsBuilder {
+		self.client.options_mut().timeout = Some(value);
+		self
+	}
+
+	/// The API key to send with these requests, overriding the client's key.
+	pub fn api_key(mut self, key: impl Into<String>) -> GetCardsBuilder {
+		self.client.options_mut().api_key = Some(key.into());
+		self
+	}
+
+	/// The most of these requests to send at once. The client's `max_concurrent_requests`
+	/// still applies on top of this.
+	pub fn max_concurrent_requests(mut self, value: NonZeroUsize) -> GetCardsBuilder {
+		self.client.options_mut().limit = Some(Arc::new(Semaphore::new(value.get())));
+		self
+	}
+
+	/// Specific fields to fetch with the cards.
+	/// 
+	/// Always includes "id" if not added.
+	pub fn select(mut self, value: impl Into<String>) -> GetCardsBuilder {
+		self.select = Some(value.into());
+		self
+	}
+
+	/// Has every card from the same set share one [`Set`](crate::Set) instead of each
+	/// holding its own copy, across all of the requests.
+	/// 
+	/// Sets are shared once the cards have been decoded, so this lowers the memory the
+	/// results hold but not the memory used while they're decoded.
+	pub fn share_sets(mut self) -> GetCardsBuilder {
+		self.share_sets = true;
+		self
+	}
+
+	/// Sends the requests to the cards endpoint.
+	/// 
+	/// The ids are split into queries short enough to fit in a URL, which are sent at the
+	/// same time, up to `max_concurrent_requests`. This is called when awaiting the
+	/// `GetCardsBuilder` as well.
+	/// 
+	/// # Errors
+	/// 
+	/// This method fails if any of the requests fails. Ids that don't match a card aren't
+	/// an error and are listed in [`BatchResult::missing`] instead.
+	/// 
+	/// # Example
+	/// 
+	/// ```no_run
+	/// # use pokemontcgio::{Client, Result};
+	/// # 
+	/// # async fn run() -> Result<()> {
+	/// let client = Client::with_api_key("YOUR_KEY");
+	/// let cards = client.get_cards(["xy1-1", "xy1-2"]).await?;
+	/// println!("Not found: {:?}", cards.missing);
+	/// # Ok(())
+	/// # }
+	/// ```
+	async fn send(self) -> Result<BatchResult<Card>> {
+		let (cards, _) = self.with_metadata().await?;
+		Ok(cards)
+	}
+
+	/// The URLs of the requests that would be sent, one for each chunk of ids.
+	pub fn urls(&self) -> Result<Vec<String>> {
+		self.searches().map(|search| search.url()).collect()
+	}
+
+	/// The requests that would be sent, one for each chunk of ids, with the API key redacted.
+	pub fn to_requests(&self) -> Result<Vec<RequestPreview>> {
+		self.searches().map(|search| search.to_request()).collect()
+	}
+
+	/// Also returns the [`ResponseMetadata`] of each response, such as its status and rate limit.
+	pub fn with_metadata(self) -> WithMetadata<GetCardsBuilder> {
+		WithMetadata { builder: self }
+	}
+
+	/// A search for each chunk of ids.
+	fn searches(&self) -> impl Iterator<Item = SearchCardsBuilder> + '_ {
+		id_queries(&self.ids).into_iter().map(|query| {
+			let search = SearchCardsBuilder::new(self.client.clone()).query(query).page_size(250);
+			match &self.select {
+				Some(select) => search.select(select),
+				None => search,
+			}
+		})
+	}
+}
+
+futurize!(GetCardsBuilder, BatchResult<Card>);
+
+impl WithMetadata<GetCardsBuilder> {
+	async fn send(self) -> Result<(BatchResult<Card>, Vec<ResponseMetadata>)> {
+		let requests = self.builder.searches().map(|search| search.with_metadata().into_future());
+		let mut cards = vec![];
+		let mut metadata = vec![];
+		for (page, meta) in future::try_join_all(requests).await? {
+			cards.extend(page.into_iter().flatten());
+			metadata.extend(meta);
+		}
+		let cards = SharedSets::new(self.builder.share_sets).share(cards);
+
+		Ok((BatchResult::new(&self.builder.ids, cards, |card| &card.id), metadata))
+	}
+}
+
+futurize!(WithMetadata<GetCardsBuilder>, (BatchResult<Card>, Vec<ResponseMetadata>));
+
+impl Client {
+	/// Convenience method to fetch several cards by id.
+	/// 
+	/// Duplicate ids are only fetched once.
+	pub fn get_cards(&self, ids: impl IntoIterator<Item = impl Into<String>>) -> GetCardsBuilder {
+		GetCardsBuilder::new(self.clone(), dedupe_ids(ids))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	
+	fn client() -> Client {
+		let poketcg_key = dotenv::var("POKETCGAPIKEY").unwrap();
+		Client::builder().api_key(poketcg_key).build()
+	}
+
+	#[tokio::test]
+	async fn test_get_cards() -> Result<()> {
+		let client = client();
+		let cards = client.get_cards(["xy1-1", "xy1-2", "not-a-card"]).await?;
+		assert!(cards.found.contains_key("xy1-1"));
+		assert!(cards.found.contains_key("xy1-2"));
+		assert_eq!(cards.missing, vec![String::from("not-a-card")]);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_cards_with_metadata() -> Result<()> {
+		let client = client();
+		let (cards, metadata) = client.get_cards(["xy1-1", "xy1-2"]).with_metadata().await?;
+		assert_eq!(cards.found.len(), 2);
+		assert_eq!(metadata.len(), 1);
+		assert_eq!(metadata[0].status, 200);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_get_cards_urls() -> Result<()> {
+		let client = Client::default();
+		let ids: Vec<String> = (1..=200).map(|i| format!("sv3pt5-{i}")).collect();
+		let urls = client.get_cards(ids).urls()?;
+		assert!(urls.len() > 1);
+		assert!(urls.iter().all(|url| url.contains("pageSize=250")));
+
+		Ok(())
+	}
+}
@@ -1,5 +1,8 @@
 mod search_cards;
 mod get_card;
+mod get_cards;
 
-pub use search_cards::SearchCardsBuilder;
-pub use get_card::GetCardBuilder;
\ No newline at end of file
+pub use search_cards::{SearchCards, SearchCardsBuilder, LenientSearchCardsBuilder, ResumableSearchCardsBuilder};
+pub use get_card::GetCardBuilder;
+pub use get_cards::GetCardsBuilder;
+pub(crate) use search_cards::SharedSets;
\ No newline at end of file
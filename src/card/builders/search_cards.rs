@@ -1,9 +1,16 @@
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::future::IntoFuture;
+use std::sync::Arc;
+use std::time::Duration;
 
-use crate::{Client, Requestable, Result};
-use crate::client::PaginatedApiResponse;
-use crate::Card;
+use futures::{stream, Stream, TryStreamExt};
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Serialize};
+
+use crate::{Client, DecodeReport, Error, Paginated, PartialResult, Progress, RequestPreview, Requestable, ResponseMetadata, Result, WithMetadata};
+use crate::client::{PaginatedApiResponse, Pager, ProgressCallback};
+use crate::{Card, Set};
 use crate::utils::futurize;
 
 /// A builder to construct the properties for the cards endpoint
@@ -13,15 +20,67 @@ use crate::utils::futurize;
 pub struct SearchCardsBuilder {
 	client: Client,
 	request: SearchCards,
+	progress: Option<ProgressCallback>,
+	limit: Option<u32>,
+	share_sets: bool,
 }
 
-#[derive(Debug, Clone, Default)]
+/// The parameters of a search on the cards endpoint.
+/// 
+/// A `SearchCards` can be stored or sent elsewhere with serde and turned back into a builder
+/// with [`Client::search_cards_from`]. To get one from a builder, call
+/// [`SearchCardsBuilder::request`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SearchCards {
+	#[serde(skip_serializing_if = "Option::is_none")]
 	query: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
 	page: Option<u32>,
+	#[serde(skip_serializing_if = "Option::is_none")]
 	page_size: Option<u32>,
+	#[serde(skip_serializing_if = "Option::is_none")]
 	order_by: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
 	select: Option<String>,
+	// Only set on the builder returned by a failed resumable search, so it isn't part of the
+	// stored form.
+	#[serde(skip)]
+	resume_page: Option<u32>,
+}
+
+impl SearchCards {
+	/// Returns a `SearchCards` for `query`.
+	/// 
+	/// For information on the syntax, go to <https://pokemontcg.guru/syntax>
+	pub fn new(query: impl Into<String>) -> SearchCards {
+		SearchCards { query: Some(query.into()), ..Default::default() }
+	}
+
+	/// The query to search with.
+	pub fn query(&self) -> Option<&str> {
+		self.query.as_deref()
+	}
+
+	/// The page to start fetching results from. If `None`, all results are fetched.
+	pub fn page(&self) -> Option<u32> {
+		self.page
+	}
+
+	/// The size of the results.
+	pub fn page_size(&self) -> Option<u32> {
+		self.page_size
+	}
+
+	/// The order of the results.
+	pub fn order_by(&self) -> Option<&str> {
+		self.order_by.as_deref()
+	}
+
+	/// The fields to fetch with the cards.
+	pub fn select(&self) -> Option<&str> {
+		self.select.as_deref()
+	}
 }
 
 impl Requestable for SearchCards {
@@ -34,7 +93,7 @@ impl Requestable for SearchCards {
 		if let Some(q) = &self.query {
 			ret.push(("q".to_string(), q.clone()));
 		}
-		if let Some(page) = &self.page {
+		if let Some(page) = self.page.or(self.resume_page) {
 			ret.push(("page".to_string(), page.to_string()));
 		}
 		if let Some(page_size) = &self.page_size {
@@ -51,9 +110,39 @@ impl Requestable for SearchCards {
 	}
 }
 
+impl Paginated for SearchCards {
+	fn page(&self) -> Option<u32> {
+		self.page
+	}
+
+	fn resume_from(&mut self, page: u32) {
+		self.resume_page = Some(page);
+	}
+}
+
 impl SearchCardsBuilder {
 	pub(crate) fn new(client: Client) -> SearchCardsBuilder {
-		SearchCardsBuilder { client, request: SearchCards::default() }
+		SearchCardsBuilder { client, request: SearchCards::default(), progress: None, limit: None, share_sets: false }
+	}
+
+	/// The longest to wait for each request to complete, overriding the client's timeout.
+	pub fn timeout(mut self, value: Duration) -> SearchCardsBuilder {
+		self.client.options_mut().timeout = Some(value);
+		self
+	}
+
+	/// The API key to send with this request, overriding the client's key.
+	pub fn api_key(mut self, key: impl Into<String>) -> SearchCardsBuilder {
+		self.client.options_mut().api_key = Some(key.into());
+		self
+	}
+
+	/// The longest the search may take to fetch all of its pages, overriding the client's deadline.
+	/// 
+	/// Once the deadline passes, the search fails with an `Error::DeadlineExceededError`.
+	pub fn deadline(mut self, value: Duration) -> SearchCardsBuilder {
+		self.client.options_mut().deadline = Some(value);
+		self
 	}
 
 	/// Query to search with.
@@ -96,6 +185,32 @@ impl SearchCardsBuilder {
 		self
 	}
 
+	/// The most cards to fetch. Pages stop being fetched once this many cards have been collected.
+	/// 
+	/// If no page size is set, the page size is lowered to the limit so no more cards are
+	/// downloaded than needed. A limit of 0 returns `None` without sending a request.
+	pub fn limit(mut self, value: u32) -> SearchCardsBuilder {
+		self.limit = Some(value);
+		self
+	}
+
+	/// Calls `callback` with the [`Progress`] of the request after each page is fetched.
+	pub fn on_progress(mut self, callback: impl Fn(&Progress) + Send + Sync + 'static) -> SearchCardsBuilder {
+		self.progress = Some(ProgressCallback::new(callback));
+		self
+	}
+
+	/// Has every card from the same set share one [`Set`] instead of each holding its own copy.
+	/// 
+	/// The first copy of each set that is decoded is kept, so all of the cards share the
+	/// same fields even if the set changes between pages. Sets are shared once each page
+	/// has been decoded, so this lowers the memory the results hold but not the memory
+	/// used while a page is decoded.
+	pub fn share_sets(mut self) -> SearchCardsBuilder {
+		self.share_sets = true;
+		self
+	}
+
 	/// Sends the request to the cards endpoint with the provided parameters.
 	/// 
 	/// This is called when awaiting the `SearchCardsBuilder` as well.
@@ -118,25 +233,274 @@ impl SearchCardsBuilder {
 	/// ```
 	async fn send(self) -> Result<Option<Vec<Card>>> {
 		let mut cards: Vec<Card> = vec![];
+		let mut sets = self.shared_sets();
+		let mut pager = self.pager();
+		while let Some(page) = pager.next().await? {
+			cards.extend(sets.share(page));
+		}
+
+		if !cards.is_empty() {
+			Ok(Some(cards))
+		} else {
+			Ok(None)
+		}
+	}
+
+	/// Streams the cards one at a time, fetching each page as the previous one runs out.
+	/// 
+	/// # Example
+	/// 
+	/// ```no_run
+	/// # use futures::TryStreamExt;
+	/// # use pokemontcgio::{Client, Result};
+	/// # 
+	/// # async fn run() -> Result<()> {
+	/// let client = Client::with_api_key("YOUR_KEY");
+	/// let mut cards = std::pin::pin!(client.search_cards().stream());
+	/// while let Some(card) = cards.try_next().await? {
+	///     println!("{}", card.id);
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn stream(self) -> impl Stream<Item = Result<Card>> {
+		let sets = self.shared_sets();
+		let pager = self.pager();
+		stream::try_unfold((pager, sets), |(mut pager, mut sets)| async move {
+			let page: Option<Vec<Card>> = pager.next().await?;
+			Ok::<_, Error>(page.map(|page| (stream::iter(sets.share(page).into_iter().map(Ok)), (pager, sets))))
+		})
+		.try_flatten()
+	}
+
+	/// Returns the number of cards matching the query without fetching them.
+	/// 
+	/// This sends a single request for one card and reads the total from the response.
+	/// 
+	/// # Example
+	/// 
+	/// ```no_run
+	/// # use pokemontcgio::{Client, Result};
+	/// # 
+	/// # async fn run() -> Result<()> {
+	/// let client = Client::with_api_key("YOUR_KEY");
+	/// let count = client.search_cards().query("name:charizard").count().await?;
+	/// println!("{count} results");
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub async fn count(self) -> Result<u32> {
+		let mut request = self.request;
+		request.page = Some(1);
+		request.page_size = Some(1);
+		request.select = Some(String::from("id"));
+		let ret: PaginatedApiResponse<IgnoredAny> = self.client.get(request).await?;
+		Ok(ret.total_count.max(0) as u32)
+	}
+
+	/// Returns whether any cards match the query, without fetching them.
+	pub async fn exists(self) -> Result<bool> {
+		Ok(self.count().await? > 0)
+	}
+
+	/// Returns the first card matching the query, fetching only that card.
+	pub async fn first(self) -> Result<Option<Card>> {
+		let cards = self.limit(1).send().await?;
+		Ok(cards.and_then(|cards| cards.into_iter().next()))
+	}
+
+	/// Decodes each card on its own instead of failing the whole request.
+	/// 
+	/// Cards that fail to decode are skipped and listed in the returned [`DecodeReport`].
+	/// 
+	/// # Example
+	/// 
+	/// ```no_run
+	/// # use pokemontcgio::{Client, Result};
+	/// # 
+	/// # async fn run() -> Result<()> {
+	/// let client = Client::with_api_key("YOUR_KEY");
+	/// let (cards, report) = client.search_cards().lenient().await?;
+	/// println!("Skipped cards: {:?}", report.ids().collect::<Vec<_>>());
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn lenient(self) -> LenientSearchCardsBuilder {
+		LenientSearchCardsBuilder { builder: self }
+	}
+
+	/// Keeps the cards already fetched if a later page fails.
+	/// 
+	/// On failure, the returned [`PartialResult`] holds the cards fetched so far and a
+	/// builder that continues from the page that failed.
+	/// 
+	/// # Example
+	/// 
+	/// ```no_run
+	/// # use pokemontcgio::{Client, Result};
+	/// # 
+	/// # async fn run() -> Result<()> {
+	/// let client = Client::with_api_key("YOUR_KEY");
+	/// let cards = match client.search_cards().resumable().await {
+	///     Ok(cards) => cards.unwrap_or_default(),
+	///     Err(partial) => {
+	///         let mut cards = partial.items;
+	///         cards.extend(partial.resume.await?.unwrap_or_default());
+	///         cards
+	///     }
+	/// };
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn resumable(self) -> ResumableSearchCardsBuilder {
+		ResumableSearchCardsBuilder { builder: self }
+	}
+
+	/// The parameters of the search, which can be stored and used again with
+	/// [`Client::search_cards_from`].
+	/// 
+	/// The page a resumed search continues from isn't stored, so a stored search starts
+	/// from the first page again.
+	pub fn request(&self) -> &SearchCards {
+		&self.request
+	}
+
+	/// The URL this request would be sent to, including the encoded query parameters.
+	pub fn url(&self) -> Result<String> {
+		Ok(self.to_request()?.url)
+	}
+
+	/// The request that would be sent, with the API key redacted.
+	pub fn to_request(&self) -> Result<RequestPreview> {
+		self.client.preview(&self.first_request())
+	}
+
+	/// Also returns the [`ResponseMetadata`] of each page's response, such as its status and rate limit.
+	pub fn with_metadata(self) -> WithMetadata<SearchCardsBuilder> {
+		WithMetadata { builder: self }
+	}
+
+	/// The request for the first page, with the page size lowered to the limit if needed.
+	fn first_request(&self) -> SearchCards {
 		let mut request = self.request.clone();
-		// Get all pages if none is specified
-		if request.page.is_none() {
-			loop {
-				let ret: PaginatedApiResponse<Card> = self.client.get(request.clone()).await?;
-				cards.extend(ret.data.unwrap_or_default());
-				if ret.page >= ret.total_count / ret.page_size {
-					break;
-				}
-				*request.page.get_or_insert(1) += 1;
+		if let Some(limit) = self.limit {
+			request.page_size = request.page_size.or(Some(limit.clamp(1, 250)));
+		}
+		request
+	}
+
+	fn shared_sets(&self) -> SharedSets {
+		SharedSets::new(self.share_sets)
+	}
+
+	fn pager(self) -> Pager<SearchCards> {
+		let request = self.first_request();
+		Pager::new(self.client, request).on_progress(self.progress).limit(self.limit)
+	}
+}
+
+futurize!(SearchCardsBuilder, Option<Vec<Card>>);
+
+/// The sets seen so far by a search, used to share one [`Set`] between the cards of each set.
+pub(crate) struct SharedSets {
+	enabled: bool,
+	sets: HashMap<String, Arc<Set>>,
+}
+
+impl SharedSets {
+	/// Returns a `SharedSets` that only shares sets if `enabled` is true.
+	pub(crate) fn new(enabled: bool) -> SharedSets {
+		SharedSets { enabled, sets: HashMap::new() }
+	}
+
+	/// Replaces the set of each card with the first copy of it that was seen.
+	pub(crate) fn share(&mut self, mut cards: Vec<Card>) -> Vec<Card> {
+		if self.enabled {
+			for set in cards.iter_mut().filter_map(|card| card.set.as_mut()) {
+				*set = self.sets.entry(set.id.clone()).or_insert_with(|| set.clone()).clone();
 			}
-		// Otherwise fetch the specified page
+		}
+		cards
+	}
+}
+
+/// A [`SearchCardsBuilder`] that skips cards which fail to decode.
+/// 
+/// To construct a `LenientSearchCardsBuilder`, call [`SearchCardsBuilder::lenient`].
+#[derive(Debug, Clone)]
+pub struct LenientSearchCardsBuilder {
+	builder: SearchCardsBuilder,
+}
+
+impl LenientSearchCardsBuilder {
+	async fn send(self) -> Result<(Option<Vec<Card>>, DecodeReport)> {
+		let mut cards: Vec<Card> = vec![];
+		let mut report = DecodeReport::default();
+		let mut sets = self.builder.shared_sets();
+		let mut pager = self.builder.pager();
+		while let Some(page) = pager.next_lenient(&mut report).await? {
+			cards.extend(sets.share(page));
+		}
+
+		if !cards.is_empty() {
+			Ok((Some(cards), report))
 		} else {
-			let ret: PaginatedApiResponse<Card> = self.client.get(request.clone()).await?;
-			cards.extend(ret.data.unwrap_or_default());
+			Ok((None, report))
 		}
-		
+	}
+}
+
+impl WithMetadata<SearchCardsBuilder> {
+	async fn send(self) -> Result<(Option<Vec<Card>>, Vec<ResponseMetadata>)> {
+		let mut cards: Vec<Card> = vec![];
+		let mut sets = self.builder.shared_sets();
+		let mut pager = self.builder.pager();
+		while let Some(page) = pager.next().await? {
+			cards.extend(sets.share(page));
+		}
+
+		let metadata = pager.into_metadata();
+		if !cards.is_empty() {
+			Ok((Some(cards), metadata))
+		} else {
+			Ok((None, metadata))
+		}
+	}
+}
+
+futurize!(WithMetadata<SearchCardsBuilder>, (Option<Vec<Card>>, Vec<ResponseMetadata>));
+
+futurize!(LenientSearchCardsBuilder, (Option<Vec<Card>>, DecodeReport));
 
-		if cards.len() > 0usize {
+/// A [`SearchCardsBuilder`] that keeps the cards already fetched if a later page fails.
+/// 
+/// To construct a `ResumableSearchCardsBuilder`, call [`SearchCardsBuilder::resumable`].
+#[derive(Debug, Clone)]
+pub struct ResumableSearchCardsBuilder {
+	builder: SearchCardsBuilder,
+}
+
+impl ResumableSearchCardsBuilder {
+	async fn send(self) -> core::result::Result<Option<Vec<Card>>, PartialResult<Card, SearchCardsBuilder>> {
+		let client = self.builder.client.clone();
+		let progress = self.builder.progress.clone();
+		let share_sets = self.builder.share_sets;
+		let mut sets = self.builder.shared_sets();
+		let mut cards: Vec<Card> = vec![];
+		let mut pager = self.builder.pager();
+		loop {
+			match pager.next().await {
+				Ok(Some(page)) => cards.extend(sets.share(page)),
+				Ok(None) => break,
+				Err(error) => {
+					let limit = pager.remaining_limit();
+					let resume = SearchCardsBuilder { client, request: pager.into_request(), progress, limit, share_sets };
+					return Err(PartialResult { items: cards, error, resume });
+				}
+			}
+		}
+
+		if !cards.is_empty() {
 			Ok(Some(cards))
 		} else {
 			Ok(None)
@@ -144,7 +508,7 @@ impl SearchCardsBuilder {
 	}
 }
 
-futurize!(SearchCardsBuilder, Option<Vec<Card>>);
+futurize!(ResumableSearchCardsBuilder, Option<Vec<Card>>, PartialResult<Card, SearchCardsBuilder>);
 
 // Client implementations
 impl Client {
@@ -152,6 +516,30 @@ impl Client {
 	pub fn search_cards(&self) -> SearchCardsBuilder {
 		SearchCardsBuilder::new(self.clone())
 	}
+
+	/// Returns a builder for a search that was stored earlier.
+	/// 
+	/// # Example
+	/// 
+	/// ```no_run
+	/// # use pokemontcgio::{Client, Result};
+	/// # use pokemontcgio::card::SearchCards;
+	/// # 
+	/// # async fn run() -> Result<()> {
+	/// let client = Client::with_api_key("YOUR_KEY");
+	/// let saved: SearchCards = serde_json::from_str(r#"{"query": "name:charizard", "page_size": 10}"#)?;
+	/// let cards = client.search_cards_from(saved).await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn search_cards_from(&self, mut request: SearchCards) -> SearchCardsBuilder {
+		let select = request.select.take();
+		let builder = SearchCardsBuilder { request, ..SearchCardsBuilder::new(self.clone()) };
+		match select {
+			Some(select) => builder.select(select),
+			None => builder,
+		}
+	}
 }
 
 #[cfg(test)]
@@ -228,4 +616,72 @@ mod tests {
 
 		Ok(())
 	}
-}
\ No newline at end of file
+
+	#[tokio::test]
+	async fn test_search_cards_count() -> Result<()> {
+		let client = client();
+		let count = client.search_cards().query("name:magikarp").count().await?;
+		assert!(count > 0);
+		assert!(client.search_cards().query("name:magikarp").exists().await?);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_search_cards_with_limit() -> Result<()> {
+		let client = client();
+		let searched_cards = client.search_cards().limit(3).await?;
+		assert_eq!(searched_cards.map(|cards| cards.len()), Some(3usize));
+		assert!(client.search_cards().first().await?.is_some());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_search_cards_to_request() -> Result<()> {
+		let client = Client::with_api_key("secret");
+		let request = client.search_cards().query("name:charizard").order_by("-number").limit(5).to_request()?;
+		assert_eq!(request.url, "https://api.pokemontcg.io/v2/cards?q=name%3Acharizard&pageSize=5&orderBy=-number");
+		assert_eq!(request.headers, vec![(String::from("x-api-key"), String::from("<redacted>"))]);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_search_cards_from_stored_request() -> Result<()> {
+		let client = Client::default();
+		let request = client.search_cards().query("name:charizard").page_size(10).select("name").request().clone();
+		let json = serde_json::to_string(&request)?;
+		assert_eq!(json, r#"{"query":"name:charizard","page_size":10,"select":"name,id"}"#);
+
+		let request: SearchCards = serde_json::from_str(r#"{"query":"name:charizard","select":"name"}"#)?;
+		assert_eq!(request.query(), Some("name:charizard"));
+		let builder = client.search_cards_from(request);
+		assert_eq!(builder.request().select(), Some("name,id"));
+
+		let mut request = SearchCards::new("name:charizard");
+		request.resume_from(3);
+		assert_eq!(serde_json::to_string(&request)?, r#"{"query":"name:charizard"}"#);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_shared_sets() -> Result<()> {
+		let page = || -> Result<Vec<Card>> {
+			Ok(serde_json::from_str(r#"[{"id": "sv1-1", "set": {"id": "sv1"}}, {"id": "sv1-2", "set": {"id": "sv1"}}, {"id": "sv2-1"}]"#)?)
+		};
+		let mut sets = SharedSets::new(true);
+		let first = sets.share(page()?);
+		let second = sets.share(page()?);
+		assert!(Arc::ptr_eq(first[0].set.as_ref().unwrap(), first[1].set.as_ref().unwrap()));
+		assert!(Arc::ptr_eq(first[0].set.as_ref().unwrap(), second[0].set.as_ref().unwrap()));
+		assert!(second[2].set.is_none());
+
+		let mut sets = SharedSets::new(false);
+		let cards = sets.share(page()?);
+		assert!(!Arc::ptr_eq(cards[0].set.as_ref().unwrap(), cards[1].set.as_ref().unwrap()));
+
+		Ok(())
+	}
+}
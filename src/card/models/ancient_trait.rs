@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+
+/// An Ancient Trait on a Pokémon card, found on cards from the Primal Clash era.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct AncientTrait {
+	/// The name of the Ancient Trait, such as "Ω Barrier".
+	pub name: String,
+	/// The text of the Ancient Trait.
+	pub text: String,
+}
@@ -1,18 +1,157 @@
 use serde::{Deserialize, Serialize};
 
+use super::EnergyType;
+
 /// Represents an attack on a card.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Attack {
 	/// The cost of the attack represented by a list of energy types.
-	pub cost: Vec<String>,
+	pub cost: Vec<EnergyType>,
 	/// The name of the attack.
 	pub name: String,
 	/// The text or description associated with the attack.
 	pub text: String,
 	/// The damage amount of the attack.
-	pub damage: Option<String>,
+	pub damage: Option<Damage>,
 	/// The total cost of the attack. For example, if it costs 2 fire energy, the
 	/// converted energy cost is simply 2.
 	pub converted_energy_cost: i32,
-}
\ No newline at end of file
+}
+
+/// The damage printed on an attack, such as "120", "120+", "30×" or "60-".
+/// 
+/// Serialized back to the string the API sent, such as "10x", unless the parts are changed.
+/// Two damages are equal when their parts are, however the string was written.
+#[derive(Debug, Clone)]
+pub struct Damage {
+	/// The number printed, if any. Some attacks, such as "×" attacks on older cards, only
+	/// print a symbol.
+	pub base: Option<u32>,
+	/// The symbol printed after the number.
+	pub modifier: DamageModifier,
+	/// The string from the API, if it isn't written the way `Display` writes the parts.
+	text: Option<String>,
+}
+
+/// The symbol after an attack's damage, which means the attack's text changes the damage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DamageModifier {
+	/// "+", the attack can do more damage.
+	Plus,
+	/// "×", the damage is multiplied.
+	Times,
+	/// "-", the attack can do less damage.
+	Minus,
+	/// The damage is fixed.
+	None,
+}
+
+impl Damage {
+	/// Returns a `Damage` of `base` followed by `modifier`.
+	pub fn new(base: Option<u32>, modifier: DamageModifier) -> Damage {
+		Damage { base, modifier, text: None }
+	}
+
+	/// Splits `value` into its number and symbol.
+	fn parse(value: &str) -> (Option<u32>, DamageModifier) {
+		let trimmed = value.trim();
+		let (number, modifier) = match trimmed.chars().last() {
+			Some('+') => (&trimmed[..trimmed.len() - 1], DamageModifier::Plus),
+			Some(c @ ('×' | 'x' | 'X')) => (&trimmed[..trimmed.len() - c.len_utf8()], DamageModifier::Times),
+			Some(c @ ('-' | '−')) => (&trimmed[..trimmed.len() - c.len_utf8()], DamageModifier::Minus),
+			_ => (trimmed, DamageModifier::None),
+		};
+
+		(number.trim().parse().ok(), modifier)
+	}
+
+	/// Writes the parts the way the API usually does.
+	fn write_parts(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if let Some(base) = self.base {
+			write!(f, "{base}")?;
+		}
+		f.write_str(match self.modifier {
+			DamageModifier::Plus => "+",
+			DamageModifier::Times => "×",
+			DamageModifier::Minus => "-",
+			DamageModifier::None => "",
+		})
+	}
+}
+
+impl From<&str> for Damage {
+	fn from(value: &str) -> Self {
+		let (base, modifier) = Damage::parse(value);
+		let mut damage = Damage::new(base, modifier);
+		if damage.to_string() != value {
+			damage.text = Some(value.to_string());
+		}
+
+		damage
+	}
+}
+
+impl PartialEq for Damage {
+	fn eq(&self, other: &Self) -> bool {
+		(self.base, self.modifier) == (other.base, other.modifier)
+	}
+}
+
+impl Eq for Damage {}
+
+impl std::hash::Hash for Damage {
+	fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+		self.base.hash(state);
+		self.modifier.hash(state);
+	}
+}
+
+impl core::fmt::Display for Damage {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		// The original string is only kept while it still parses to the same parts.
+		match &self.text {
+			Some(text) if Damage::parse(text) == (self.base, self.modifier) => f.write_str(text),
+			_ => self.write_parts(f),
+		}
+	}
+}
+
+impl Serialize for Damage {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+		serializer.collect_str(self)
+	}
+}
+
+impl<'de> Deserialize<'de> for Damage {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+		Ok(Damage::from(String::deserialize(deserializer)?.as_str()))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_damage_from_str() {
+		assert_eq!(Damage::from("120+"), Damage::new(Some(120), DamageModifier::Plus));
+		assert_eq!(Damage::from("30×"), Damage::new(Some(30), DamageModifier::Times));
+		assert_eq!(Damage::from("60-"), Damage::new(Some(60), DamageModifier::Minus));
+		assert_eq!(Damage::from(""), Damage::new(None, DamageModifier::None));
+		let Damage { base, modifier, .. } = Damage::from("10x");
+		assert_eq!((base, modifier), (Some(10), DamageModifier::Times));
+	}
+
+	#[test]
+	fn test_damage_round_trips() -> serde_json::Result<()> {
+		let mut damage: Damage = serde_json::from_str(r#""10x""#)?;
+		assert_eq!(damage.modifier, DamageModifier::Times);
+		assert_eq!(serde_json::to_string(&damage)?, r#""10x""#);
+
+		damage.base = Some(20);
+		assert_eq!(serde_json::to_string(&damage)?, r#""20×""#);
+
+		Ok(())
+	}
+}
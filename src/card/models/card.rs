@@ -1,4 +1,5 @@
-use std::collections::HashMap;
+use std::sync::Arc;
+
 use serde::{Deserialize, Serialize};
 
 use super::*;
@@ -13,64 +14,55 @@ pub struct Card {
 	/// The name of the card
 	pub name: Option<String>,
 	/// The supertype of the card, such as Pokémon, Energy, or Trainer.
-	pub supertype: Option<String>,
+	pub supertype: Option<Supertype>,
 	/// A list of subtypes, such as Basic, EX, Mega, Rapid Strike, etc.
-	pub subtypes: Option<Vec<String>>,
+	pub subtypes: Option<Vec<Subtype>>,
 	/// The level of the card. This only pertains to cards from older sets and those of supertype Pokémon.
 	pub level: Option<String>,
 	/// The hit points of the card.
 	pub hp: Option<String>,
 	/// The energy types for a card, such as Fire, Water, Grass, etc.
-	pub types: Option<Vec<String>>,
+	pub types: Option<Vec<EnergyType>>,
 	/// Which Pokémon this card evolves from.
 	pub evolves_from: Option<String>,
 	/// Which Pokémon this card evolves to. Can be multiple, for example, Eevee.
 	pub evolves_to: Option<Vec<String>>,
 	/// Any rules associated with the card. For example, VMAX rules, Mega rules, or various trainer rules.
 	pub rules: Option<Vec<String>>,
-	/// The ancient trait for a given card. An ancient trait has the following fields:
-	/// 
-	/// name - String - The name of the ancient trait.
-	/// 
-	/// test - String - The text value of the ancient trait.
-	pub ancient_trait: Option<HashMap<String, String>>,
+	/// The [`AncientTrait`] for a given card.
+	pub ancient_trait: Option<AncientTrait>,
 	/// One or more [`Ability`]s for a given card.
 	pub abilities: Option<Vec<Ability>>,
 	/// One or more [`Attack`]s for a given card.
 	pub attacks: Option<Vec<Attack>>,
-	/// One or more weaknesses for a given card. A weakness has the following fields:
-	/// 
-	/// type - String - The type of weakness, such as Fire or Water.
-	/// 
-	/// value - String - The value of the weakness.
-	pub weaknesses: Option<Vec<HashMap<String, String>>>,
-	/// One or more resistances for a given card. A resistance has the following fields:
-	/// 
-	/// type - String - The type of resistance, such as Fire or Water.
-	/// 
-	/// value - String - The value of the resistance.
-	pub resistances: Option<Vec<HashMap<String, String>>>,
+	/// One or more [`Weakness`]es for a given card.
+	pub weaknesses: Option<Vec<Weakness>>,
+	/// One or more [`Resistance`]s for a given card.
+	pub resistances: Option<Vec<Resistance>>,
 	/// A list of costs it takes to retreat and return the card to your bench. Each cost is an energy type, such as Water or Fire.
-	pub retreat_cost: Option<Vec<String>>,
+	pub retreat_cost: Option<Vec<EnergyType>>,
 	/// The converted retreat cost for a card is the count of energy types found within the retreat_cost field. For
 	/// example, ["Fire", "Water"] has a converted retreat cost of 2.
 	pub converted_retreat_cost: Option<i32>,
 	/// The [`Set`] the card belongs to.
-	pub set: Option<Set>,
-	/// The number of the card.
-	pub number: Option<String>,
+	/// 
+	/// Cards fetched with [`SearchCardsBuilder::share_sets`](crate::card::SearchCardsBuilder::share_sets)
+	/// or [`GetCardsBuilder::share_sets`](crate::card::GetCardsBuilder::share_sets) share one
+	/// `Set` for each set once they've been decoded.
+	pub set: Option<Arc<Set>>,
+	/// The [`CardNumber`] of the card in its set.
+	pub number: Option<CardNumber>,
 	/// The artist of the card.
 	pub artist: Option<String>,
 	/// The rarity of the card, such as "Common" or "Rare Rainbow".
-	pub rarity: Option<String>,
+	pub rarity: Option<Rarity>,
 	/// The flavor text of the card. This is the text that can be found on some Pokémon cards
 	/// that is usually italicized near the bottom of the card.
 	pub flavor_text: Option<String>,
 	/// The national pokedex numbers associated with any Pokémon featured on a given card.
 	pub national_pokedex_numbers: Option<Vec<i32>>,
-	/// The legalities for a given card. A legality will not be present in the hash if it is not legal. 
-	/// If it is legal or banned, it will be present.
-	pub legalities: Option<HashMap<String, String>>,
+	/// The [`Legalities`] for a given card.
+	pub legalities: Option<Legalities>,
 	/// A letter symbol found on each card that identifies whether it is legal to use in tournament play. 
 	/// Regulation marks were introduced on cards in the Sword & Shield Series.
 	pub regulation_mark: Option<String>,
@@ -88,4 +80,226 @@ impl PartialEq for Card {
 	}
 }
 
-impl Eq for Card {}
\ No newline at end of file
+impl Eq for Card {}
+
+impl std::hash::Hash for Card {
+	fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+		self.id.hash(state);
+	}
+}
+
+impl PartialOrd for Card {
+	fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for Card {
+	fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+		crate::ids::cmp_card_ids(&self.id, &other.id)
+	}
+}
+
+impl Card {
+	/// The hit points of the card as a number, if it has any.
+	pub fn hp_value(&self) -> Option<u32> {
+		self.hp.as_deref()?.trim().parse().ok()
+	}
+
+	/// Whether the card is numbered past the set's printed total, such as 201/198.
+	/// 
+	/// Numbers with a prefix, such as Trainer Gallery "TG12", belong to their own subset
+	/// and aren't counted. Returns `false` if the number or set wasn't fetched.
+	pub fn is_secret_rare(&self) -> bool {
+		let printed_total = self.set.as_ref().and_then(|set| set.printed_total);
+		match (&self.number, printed_total) {
+			(Some(number), Some(total)) if number.prefix().is_empty() => {
+				number.number().is_some_and(|n| i64::from(n) > total)
+			},
+			_ => false,
+		}
+	}
+
+	/// Whether the card is legal in `format`.
+	pub fn is_legal_in(&self, format: Format) -> bool {
+		self.legalities.as_ref().is_some_and(|legalities| legalities.is_legal_in(format))
+	}
+}
+
+/// The subtypes of Pokémon and Trainer cards with a rule box.
+const RULE_BOX_SUBTYPES: [Subtype; 13] = [
+	Subtype::Ex,
+	Subtype::LegacyEx,
+	Subtype::Gx,
+	Subtype::TagTeam,
+	Subtype::V,
+	Subtype::Vmax,
+	Subtype::Vstar,
+	Subtype::VUnion,
+	Subtype::Mega,
+	Subtype::Break,
+	Subtype::Legend,
+	Subtype::PrismStar,
+	Subtype::Radiant,
+];
+
+/// Classification helpers
+impl Card {
+	fn has_subtype(&self, subtype: &Subtype) -> bool {
+		self.subtypes.as_ref().is_some_and(|subtypes| subtypes.contains(subtype))
+	}
+
+	fn rules(&self) -> impl Iterator<Item = &str> {
+		self.rules.iter().flatten().map(String::as_str)
+	}
+
+	/// Whether the card is known to be a Pokémon. Cards fetched without their supertype aren't.
+	fn is_pokemon(&self) -> bool {
+		self.supertype == Some(Supertype::Pokemon)
+	}
+
+	/// The number of Prize cards from a rule like "When your Pokémon ex is Knocked Out, your
+	/// opponent takes 2 Prize cards."
+	fn prize_rule(&self) -> Option<u8> {
+		if !self.is_pokemon() {
+			return None;
+		}
+		self.rules()
+			.filter(|rule| rule.contains("Knocked Out"))
+			.find_map(|rule| {
+				let end = rule.find("Prize card")?;
+				rule[..end].split_whitespace().last()?.parse().ok()
+			})
+	}
+
+	/// The evolution stage of the card, or `None` if it isn't a Pokémon or was fetched
+	/// without its supertype.
+	pub fn stage(&self) -> Option<Stage> {
+		if !self.is_pokemon() {
+			return None;
+		}
+		Stage::from_subtypes(self.subtypes.as_deref().unwrap_or_default())
+	}
+
+	/// Whether the card has a rule box, such as a Pokémon ex, V, GX or Radiant Pokémon, or a
+	/// Prism Star card.
+	pub fn is_rule_box(&self) -> bool {
+		RULE_BOX_SUBTYPES.iter().any(|subtype| self.has_subtype(subtype))
+			|| self.prize_rule().is_some_and(|prizes| prizes > 1)
+	}
+
+	/// The number of Prize cards the opponent takes when this card is Knocked Out: 1, 2 or 3.
+	/// 
+	/// This comes from the card's rules when they state it, and otherwise from its subtypes.
+	pub fn prize_value(&self) -> u8 {
+		if let Some(prizes) = self.prize_rule() {
+			return prizes;
+		}
+		if [Subtype::TagTeam, Subtype::Vmax, Subtype::VUnion].iter().any(|subtype| self.has_subtype(subtype)) {
+			3
+		} else if [Subtype::Ex, Subtype::LegacyEx, Subtype::Gx, Subtype::V, Subtype::Vstar, Subtype::Mega, Subtype::Legend].iter().any(|subtype| self.has_subtype(subtype)) {
+			2
+		} else {
+			1
+		}
+	}
+
+	/// Whether the card is an ACE SPEC, which a deck can only have one of.
+	pub fn is_ace_spec(&self) -> bool {
+		self.has_subtype(&Subtype::AceSpec)
+			|| self.rarity == Some(Rarity::AceSpecRare)
+			|| self.rules().any(|rule| rule.contains("ACE SPEC"))
+	}
+
+	/// Whether the card is a Radiant Pokémon, which a deck can only have one of.
+	pub fn is_radiant(&self) -> bool {
+		self.has_subtype(&Subtype::Radiant) || self.rules().any(|rule| rule.starts_with("Radiant Pokémon Rule"))
+	}
+
+	/// Whether the card is a Tera Pokémon, which takes no damage from attacks while on the Bench.
+	pub fn is_tera(&self) -> bool {
+		self.has_subtype(&Subtype::Tera) || self.rules().any(|rule| rule.starts_with("Tera:"))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn card(json: &str) -> Card {
+		serde_json::from_str(json).unwrap()
+	}
+
+	#[test]
+	fn test_card_classification() {
+		let charizard = card(r#"{
+			"id": "sv3-125",
+			"supertype": "Pokémon",
+			"hp": "330",
+			"subtypes": ["Stage 2", "ex", "Tera"],
+			"rules": [
+				"Tera: As long as this Pokémon is on your Bench, prevent all damage done to this Pokémon by attacks (both yours and your opponent's).",
+				"Pokémon ex rule: When your Pokémon ex is Knocked Out, your opponent takes 2 Prize cards."
+			]
+		}"#);
+		assert_eq!(charizard.stage(), Some(Stage::Stage2));
+		assert!(charizard.is_rule_box());
+		assert!(charizard.is_tera());
+		assert_eq!(charizard.prize_value(), 2);
+		assert_eq!(charizard.hp_value(), Some(330));
+
+		let vmax = card(r#"{"id": "swsh3-20", "supertype": "Pokémon", "subtypes": ["VMAX"]}"#);
+		assert_eq!(vmax.stage(), Some(Stage::Vmax));
+		assert_eq!(vmax.prize_value(), 3);
+
+		let radiant = card(r#"{"id": "swsh10-20", "supertype": "Pokémon", "subtypes": ["Basic", "Radiant"]}"#);
+		assert!(radiant.is_radiant() && radiant.is_rule_box());
+		assert_eq!(radiant.prize_value(), 1);
+	}
+
+	#[test]
+	fn test_card_is_secret_rare() {
+		let secret = card(r#"{"id": "sv1-244", "number": "244", "set": {"id": "sv1", "printedTotal": 198}}"#);
+		assert!(secret.is_secret_rare());
+		let last = card(r#"{"id": "sv1-198", "number": "198", "set": {"id": "sv1", "printedTotal": 198}}"#);
+		assert!(!last.is_secret_rare());
+	}
+
+	#[test]
+	fn test_trainer_classification() {
+		let trainer = card(r#"{
+			"id": "sv5-157",
+			"supertype": "Trainer",
+			"subtypes": ["Item", "ACE SPEC"],
+			"rules": ["You can't have more than 1 ACE SPEC card in your deck."]
+		}"#);
+		assert_eq!(trainer.stage(), None);
+		assert!(trainer.is_ace_spec());
+		assert!(!trainer.is_rule_box());
+	}
+
+	#[test]
+	fn test_card_without_supertype_has_no_stage() {
+		// A Basic Energy fetched with a `select` that leaves out the supertype.
+		let energy = card(r#"{"id": "sve-1", "subtypes": ["Basic"]}"#);
+		assert_eq!(energy.stage(), None);
+	}
+
+	#[test]
+	fn test_card_hash_and_ord_by_id() {
+		let mut cards = vec![card(r#"{"id": "xy1-2"}"#), card(r#"{"id": "xy1-1", "name": "Venusaur-EX"}"#), card(r#"{"id": "xy1-1"}"#)];
+		cards.sort();
+		assert_eq!(cards[0].id, "xy1-1");
+		let unique: std::collections::HashSet<Card> = cards.into_iter().collect();
+		assert_eq!(unique.len(), 2);
+	}
+
+	#[test]
+	fn test_card_ord_matches_card_id() {
+		let mut cards = [card(r#"{"id": "sv1-10"}"#), card(r#"{"id": "sv1-2"}"#)];
+		cards.sort();
+		assert_eq!(cards[0].id, "sv1-2");
+		let ids: Vec<crate::CardId> = cards.iter().map(|card| card.id.parse().unwrap()).collect();
+		assert!(ids[0] < ids[1]);
+	}
+}
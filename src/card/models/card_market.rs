@@ -12,6 +12,14 @@ pub struct CardMarket {
 	pub prices: Option<CardMarketPrices>,
 }
 
+#[cfg(feature = "chrono")]
+impl CardMarket {
+	/// The date the prices were last updated.
+	pub fn updated(&self) -> Option<chrono::NaiveDate> {
+		crate::utils::parse_date(&self.updated_at)
+	}
+}
+
 /// Holds all of the Card Market price data. All prices are listed in Euros.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -0,0 +1,133 @@
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Serialize};
+
+/// The collector number printed on a card, such as "4", "123a", "TG12" or "SWSH001".
+/// 
+/// Numbers are split into a prefix, a numeric part and a suffix, and order the way cards
+/// are kept in a binder: numbers without a prefix come first, in numeric order, followed
+/// by each prefix's numbers, and numbers without digits, such as Unown "?", go last.
+/// Serialized as the original string.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CardNumber {
+	text: String,
+	prefix: String,
+	number: Option<u32>,
+	suffix: String,
+}
+
+impl CardNumber {
+	/// The letters before the number, such as "TG" in "TG12". Empty for most cards and for
+	/// numbers without digits.
+	pub fn prefix(&self) -> &str {
+		&self.prefix
+	}
+
+	/// The numeric part, such as 12 in "TG12". `None` if the number has no digits, such as
+	/// Unown "?". Numbers too large for a `u32` are saturated to `u32::MAX`.
+	pub fn number(&self) -> Option<u32> {
+		self.number
+	}
+
+	/// Anything after the number, such as "a" in "123a". Empty for most cards, and the whole
+	/// number for numbers without digits.
+	pub fn suffix(&self) -> &str {
+		&self.suffix
+	}
+
+	/// The number as printed.
+	pub fn as_str(&self) -> &str {
+		&self.text
+	}
+}
+
+impl From<&str> for CardNumber {
+	fn from(value: &str) -> Self {
+		let trimmed = value.trim();
+		// Numbers without digits are kept whole in the suffix, so they have no prefix.
+		let digits_start = trimmed.find(|c: char| c.is_ascii_digit()).unwrap_or(0);
+		let (prefix, rest) = trimmed.split_at(digits_start);
+		let digits_end = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+		let (digits, suffix) = rest.split_at(digits_end);
+
+		CardNumber {
+			text: value.to_string(),
+			prefix: prefix.to_string(),
+			// All ASCII digits, so parsing only fails when the number is empty or too large.
+			number: (!digits.is_empty()).then(|| digits.parse().unwrap_or(u32::MAX)),
+			suffix: suffix.to_string(),
+		}
+	}
+}
+
+impl std::str::FromStr for CardNumber {
+	type Err = std::convert::Infallible;
+
+	fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+		Ok(CardNumber::from(s))
+	}
+}
+
+impl Ord for CardNumber {
+	fn cmp(&self, other: &Self) -> Ordering {
+		// Numbers without digits go after the rest.
+		self.number.is_none().cmp(&other.number.is_none())
+			.then_with(|| self.prefix.cmp(&other.prefix))
+			.then_with(|| self.number.cmp(&other.number))
+			.then_with(|| self.suffix.cmp(&other.suffix))
+			.then_with(|| self.text.cmp(&other.text))
+	}
+}
+
+impl PartialOrd for CardNumber {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl core::fmt::Display for CardNumber {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(&self.text)
+	}
+}
+
+impl Serialize for CardNumber {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+		serializer.serialize_str(&self.text)
+	}
+}
+
+impl<'de> Deserialize<'de> for CardNumber {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+		Ok(CardNumber::from(String::deserialize(deserializer)?.as_str()))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_card_number_parts() {
+		let number = CardNumber::from("TG12");
+		assert_eq!((number.prefix(), number.number(), number.suffix()), ("TG", Some(12), ""));
+		let number = CardNumber::from("123a");
+		assert_eq!((number.prefix(), number.number(), number.suffix()), ("", Some(123), "a"));
+		let number = CardNumber::from("SWSH001");
+		assert_eq!((number.prefix(), number.number(), number.to_string().as_str()), ("SWSH", Some(1), "SWSH001"));
+		let number = CardNumber::from("?");
+		assert_eq!((number.prefix(), number.number(), number.suffix()), ("", None, "?"));
+		assert_eq!(CardNumber::from("99999999999").number(), Some(u32::MAX));
+	}
+
+	#[test]
+	fn test_card_number_binder_order() {
+		let mut numbers: Vec<CardNumber> = ["TG2", "?", "100", "10", "A", "2", "10a", "GG70", "!", "TG12", "1"]
+			.into_iter()
+			.map(CardNumber::from)
+			.collect();
+		numbers.sort();
+		let numbers: Vec<&str> = numbers.iter().map(CardNumber::as_str).collect();
+		assert_eq!(numbers, vec!["1", "2", "10", "10a", "100", "GG70", "TG2", "TG12", "!", "?", "A"]);
+	}
+}
@@ -0,0 +1,53 @@
+use crate::utils::string_enum;
+
+string_enum! {
+	/// An energy type, used for a card's types, attack costs, retreat costs, weaknesses
+	/// and resistances.
+	pub enum EnergyType {
+		/// Colorless energy, which can be paid with energy of any type.
+		Colorless => "Colorless",
+		/// Darkness energy.
+		Darkness => "Darkness",
+		/// Dragon energy.
+		Dragon => "Dragon",
+		/// Fairy energy.
+		Fairy => "Fairy",
+		/// Fighting energy.
+		Fighting => "Fighting",
+		/// Fire energy.
+		Fire => "Fire",
+		/// Grass energy.
+		Grass => "Grass",
+		/// Lightning energy.
+		Lightning => "Lightning",
+		/// Metal energy.
+		Metal => "Metal",
+		/// Psychic energy.
+		Psychic => "Psychic",
+		/// Water energy.
+		Water => "Water",
+		/// The cost of an attack that needs no energy.
+		Free => "Free",
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_energy_type_from_str() {
+		assert_eq!(EnergyType::from("Fighting"), EnergyType::Fighting);
+		assert_eq!(EnergyType::from("Fightning"), EnergyType::Unknown(String::from("Fightning")));
+		assert_eq!(EnergyType::Fighting.to_string(), "Fighting");
+	}
+
+	#[test]
+	fn test_energy_type_serde() -> serde_json::Result<()> {
+		let types: Vec<EnergyType> = serde_json::from_str(r#"["Fire", "Aether"]"#)?;
+		assert_eq!(types, vec![EnergyType::Fire, EnergyType::Unknown(String::from("Aether"))]);
+		assert_eq!(serde_json::to_string(&types)?, r#"["Fire","Aether"]"#);
+
+		Ok(())
+	}
+}
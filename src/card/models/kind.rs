@@ -0,0 +1,291 @@
+use super::*;
+
+/// A [`Card`] viewed as the kind of card it is, exposing only the fields that apply.
+/// 
+/// Returned by [`Card::kind`]. The name is always present. List fields are returned as empty
+/// slices when the card has none, or when they weren't fetched because of a `select`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardKind<'a> {
+	/// A Pokémon card.
+	Pokemon(PokemonCard<'a>),
+	/// A Trainer card.
+	Trainer(TrainerCard<'a>),
+	/// An Energy card.
+	Energy(EnergyCard<'a>),
+}
+
+/// The kind of a Trainer card, from its subtypes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TrainerKind {
+	/// An Item card.
+	Item,
+	/// A Supporter card.
+	Supporter,
+	/// A Stadium card.
+	Stadium,
+	/// A Pokémon Tool card.
+	Tool,
+}
+
+/// The kind of an Energy card, from its subtypes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EnergyKind {
+	/// A Basic Energy card.
+	Basic,
+	/// A Special Energy card.
+	Special,
+}
+
+/// A Pokémon card. See [`CardKind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PokemonCard<'a> {
+	card: &'a Card,
+	name: &'a str,
+}
+
+/// A Trainer card. See [`CardKind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrainerCard<'a> {
+	card: &'a Card,
+	name: &'a str,
+}
+
+/// An Energy card. See [`CardKind`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct EnergyCard<'a> {
+	card: &'a Card,
+	name: &'a str,
+}
+
+impl Card {
+	/// Returns a view of the card for its supertype.
+	/// 
+	/// Returns `None` if the supertype is unknown, or if the supertype or name weren't
+	/// fetched because of a `select`.
+	pub fn kind(&self) -> Option<CardKind<'_>> {
+		let name = self.name.as_deref()?;
+		match self.supertype.as_ref()? {
+			Supertype::Pokemon => Some(CardKind::Pokemon(PokemonCard { card: self, name })),
+			Supertype::Trainer => Some(CardKind::Trainer(TrainerCard { card: self, name })),
+			Supertype::Energy => Some(CardKind::Energy(EnergyCard { card: self, name })),
+			Supertype::Unknown(_) => None,
+		}
+	}
+}
+
+fn slice<T>(value: &Option<Vec<T>>) -> &[T] {
+	value.as_deref().unwrap_or_default()
+}
+
+impl<'a> PokemonCard<'a> {
+	/// The full card.
+	pub fn card(&self) -> &'a Card {
+		self.card
+	}
+
+	/// The name of the card.
+	pub fn name(&self) -> &'a str {
+		self.name
+	}
+
+	/// The hit points of the card, or `None` if they weren't fetched or aren't a number.
+	pub fn hp(&self) -> Option<u32> {
+		self.card.hp_value()
+	}
+
+	/// The evolution stage of the card.
+	pub fn stage(&self) -> Option<Stage> {
+		self.card.stage()
+	}
+
+	/// The energy types of the card.
+	pub fn types(&self) -> &'a [EnergyType] {
+		slice(&self.card.types)
+	}
+
+	/// The subtypes of the card.
+	pub fn subtypes(&self) -> &'a [Subtype] {
+		slice(&self.card.subtypes)
+	}
+
+	/// Which Pokémon this card evolves from.
+	pub fn evolves_from(&self) -> Option<&'a str> {
+		self.card.evolves_from.as_deref()
+	}
+
+	/// Which Pokémon this card evolves to.
+	pub fn evolves_to(&self) -> &'a [String] {
+		slice(&self.card.evolves_to)
+	}
+
+	/// The abilities of the card.
+	pub fn abilities(&self) -> &'a [Ability] {
+		slice(&self.card.abilities)
+	}
+
+	/// The attacks of the card.
+	pub fn attacks(&self) -> &'a [Attack] {
+		slice(&self.card.attacks)
+	}
+
+	/// The Ancient Trait of the card.
+	pub fn ancient_trait(&self) -> Option<&'a AncientTrait> {
+		self.card.ancient_trait.as_ref()
+	}
+
+	/// The weaknesses of the card.
+	pub fn weaknesses(&self) -> &'a [Weakness] {
+		slice(&self.card.weaknesses)
+	}
+
+	/// The resistances of the card.
+	pub fn resistances(&self) -> &'a [Resistance] {
+		slice(&self.card.resistances)
+	}
+
+	/// The energy it costs to retreat the card.
+	pub fn retreat_cost(&self) -> &'a [EnergyType] {
+		slice(&self.card.retreat_cost)
+	}
+
+	/// The rules of the card, such as the Pokémon ex rule.
+	pub fn rules(&self) -> &'a [String] {
+		slice(&self.card.rules)
+	}
+
+	/// The national Pokédex numbers of the Pokémon on the card.
+	pub fn national_pokedex_numbers(&self) -> &'a [i32] {
+		slice(&self.card.national_pokedex_numbers)
+	}
+
+	/// The number of Prize cards the opponent takes when the card is Knocked Out.
+	pub fn prize_value(&self) -> u8 {
+		self.card.prize_value()
+	}
+}
+
+impl<'a> TrainerCard<'a> {
+	/// The full card.
+	pub fn card(&self) -> &'a Card {
+		self.card
+	}
+
+	/// The name of the card.
+	pub fn name(&self) -> &'a str {
+		self.name
+	}
+
+	/// Whether the card is an Item, Supporter, Stadium or Pokémon Tool.
+	pub fn kind(&self) -> Option<TrainerKind> {
+		// Older Pokémon Tools are also listed as Items.
+		[
+			(Subtype::PokemonTool, TrainerKind::Tool),
+			(Subtype::PokemonToolF, TrainerKind::Tool),
+			(Subtype::Supporter, TrainerKind::Supporter),
+			(Subtype::Stadium, TrainerKind::Stadium),
+			(Subtype::Item, TrainerKind::Item),
+		]
+		.into_iter()
+		.find(|(subtype, _)| self.subtypes().contains(subtype))
+		.map(|(_, kind)| kind)
+	}
+
+	/// The subtypes of the card.
+	pub fn subtypes(&self) -> &'a [Subtype] {
+		slice(&self.card.subtypes)
+	}
+
+	/// The text of the card, along with rules such as "You may play only 1 Supporter card
+	/// during your turn."
+	pub fn rules(&self) -> &'a [String] {
+		slice(&self.card.rules)
+	}
+
+	/// Whether the card is an ACE SPEC.
+	pub fn is_ace_spec(&self) -> bool {
+		self.card.is_ace_spec()
+	}
+}
+
+impl<'a> EnergyCard<'a> {
+	/// The full card.
+	pub fn card(&self) -> &'a Card {
+		self.card
+	}
+
+	/// The name of the card.
+	pub fn name(&self) -> &'a str {
+		self.name
+	}
+
+	/// Whether the card is a Basic or Special Energy.
+	pub fn kind(&self) -> Option<EnergyKind> {
+		if self.subtypes().contains(&Subtype::Special) {
+			Some(EnergyKind::Special)
+		} else if self.subtypes().contains(&Subtype::Basic) {
+			Some(EnergyKind::Basic)
+		} else {
+			None
+		}
+	}
+
+	/// The subtypes of the card.
+	pub fn subtypes(&self) -> &'a [Subtype] {
+		slice(&self.card.subtypes)
+	}
+
+	/// The text of the card.
+	pub fn rules(&self) -> &'a [String] {
+		slice(&self.card.rules)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn card(json: serde_json::Value) -> Card {
+		serde_json::from_value(json).unwrap()
+	}
+
+	#[test]
+	fn test_pokemon_kind() {
+		let card = card(serde_json::json!({"id": "sv1-1", "name": "Sprigatito", "supertype": "Pokémon", "hp": "70", "retreatCost": ["Colorless"]}));
+		match card.kind() {
+			Some(CardKind::Pokemon(pokemon)) => {
+				assert_eq!(pokemon.name(), "Sprigatito");
+				assert_eq!(pokemon.hp(), Some(70));
+				assert_eq!(pokemon.retreat_cost(), &[EnergyType::Colorless]);
+				assert!(pokemon.attacks().is_empty());
+			},
+			kind => panic!("expected a Pokémon, got {kind:?}"),
+		}
+	}
+
+	#[test]
+	fn test_pokemon_kind_without_hp() {
+		let card = card(serde_json::json!({"id": "sv1-1", "name": "Sprigatito", "supertype": "Pokémon", "hp": "??"}));
+		match card.kind() {
+			Some(CardKind::Pokemon(pokemon)) => assert_eq!(pokemon.hp(), None),
+			kind => panic!("expected a Pokémon, got {kind:?}"),
+		}
+	}
+
+	#[test]
+	fn test_kind_needs_required_fields() {
+		let pokemon = card(serde_json::json!({"id": "sv1-1", "supertype": "Pokémon", "hp": "70"}));
+		assert_eq!(pokemon.kind(), None);
+		let trainer = card(serde_json::json!({"id": "sv2-1", "supertype": "Trainer"}));
+		assert_eq!(trainer.kind(), None);
+	}
+
+	#[test]
+	fn test_trainer_and_energy_kinds() {
+		let tool = card(serde_json::json!({"id": "sv2-1", "name": "Bravery Charm", "supertype": "Trainer", "subtypes": ["Item", "Pokémon Tool"]}));
+		assert!(matches!(tool.kind(), Some(CardKind::Trainer(t)) if t.kind() == Some(TrainerKind::Tool)));
+		let energy = card(serde_json::json!({"id": "sv2-2", "name": "Jet Energy", "supertype": "Energy", "subtypes": ["Special"]}));
+		assert!(matches!(energy.kind(), Some(CardKind::Energy(e)) if e.kind() == Some(EnergyKind::Special)));
+		let unknown = card(serde_json::json!({"id": "sv2-3"}));
+		assert_eq!(unknown.kind(), None);
+	}
+}
@@ -0,0 +1,146 @@
+use serde::{Deserialize, Serialize};
+
+/// The formats a card or set is legal or banned in.
+/// 
+/// A format is `None` when the card or set isn't legal in it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Legalities {
+	/// Legality in the Standard format.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub standard: Option<Legality>,
+	/// Legality in the Expanded format.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub expanded: Option<Legality>,
+	/// Legality in the Unlimited format.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub unlimited: Option<Legality>,
+}
+
+/// Whether a card or set is allowed in a format it appears in.
+/// 
+/// Values are matched case-insensitively, and any other value is kept in `Unknown` so new
+/// values don't fail to decode.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Legality {
+	/// Allowed in the format.
+	Legal,
+	/// Banned from the format.
+	Banned,
+	/// A value this version of the crate doesn't know about.
+	Unknown(String),
+}
+
+/// A format of play.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Format {
+	/// The Standard format.
+	Standard,
+	/// The Expanded format.
+	Expanded,
+	/// The Unlimited format.
+	Unlimited,
+}
+
+impl Legalities {
+	/// The legality in `format`, or `None` if it isn't legal there.
+	pub fn get(&self, format: Format) -> Option<&Legality> {
+		match format {
+			Format::Standard => self.standard.as_ref(),
+			Format::Expanded => self.expanded.as_ref(),
+			Format::Unlimited => self.unlimited.as_ref(),
+		}
+	}
+
+	/// Whether it's legal in `format`.
+	pub fn is_legal_in(&self, format: Format) -> bool {
+		self.get(format) == Some(&Legality::Legal)
+	}
+
+	/// Whether it's banned from `format`.
+	pub fn is_banned_in(&self, format: Format) -> bool {
+		self.get(format) == Some(&Legality::Banned)
+	}
+}
+
+impl Legality {
+	/// The value as the API sends it.
+	pub fn as_str(&self) -> &str {
+		match self {
+			Legality::Legal => "Legal",
+			Legality::Banned => "Banned",
+			Legality::Unknown(value) => value,
+		}
+	}
+}
+
+impl From<&str> for Legality {
+	fn from(value: &str) -> Self {
+		let trimmed = value.trim();
+		if trimmed.eq_ignore_ascii_case("Legal") {
+			Legality::Legal
+		} else if trimmed.eq_ignore_ascii_case("Banned") {
+			Legality::Banned
+		} else {
+			Legality::Unknown(value.to_string())
+		}
+	}
+}
+
+impl std::str::FromStr for Legality {
+	type Err = std::convert::Infallible;
+
+	fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+		Ok(Legality::from(s))
+	}
+}
+
+impl core::fmt::Display for Legality {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.write_str(self.as_str())
+	}
+}
+
+impl Serialize for Legality {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+		serializer.serialize_str(self.as_str())
+	}
+}
+
+impl<'de> Deserialize<'de> for Legality {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+		let value = Legality::from(String::deserialize(deserializer)?.as_str());
+		if let Legality::Unknown(value) = &value {
+			crate::client::unknown_value(value)?;
+		}
+		Ok(value)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_legalities_serde() -> serde_json::Result<()> {
+		let json = r#"{"expanded":"Banned","unlimited":"Legal"}"#;
+		let legalities: Legalities = serde_json::from_str(json)?;
+		assert!(!legalities.is_legal_in(Format::Standard));
+		assert!(legalities.is_banned_in(Format::Expanded));
+		assert!(legalities.is_legal_in(Format::Unlimited));
+		assert_eq!(serde_json::to_string(&legalities)?, json);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_legality_is_case_insensitive_with_fallback() -> serde_json::Result<()> {
+		let legalities: Legalities = serde_json::from_str(r#"{"standard":"legal","expanded":"BANNED","unlimited":"Restricted"}"#)?;
+		assert!(legalities.is_legal_in(Format::Standard));
+		assert!(legalities.is_banned_in(Format::Expanded));
+		assert_eq!(legalities.unlimited, Some(Legality::Unknown(String::from("Restricted"))));
+		assert!(!legalities.is_legal_in(Format::Unlimited));
+		assert_eq!(serde_json::to_string(&legalities.unlimited)?, r#""Restricted""#);
+
+		Ok(())
+	}
+}
@@ -1,14 +1,32 @@
 mod ability;
+mod ancient_trait;
 mod attack;
 mod card_market;
 mod card;
+mod card_number;
+mod energy_type;
 mod images;
+mod kind;
+mod legalities;
+mod rarity;
+mod subtype;
+mod supertype;
 mod tcg_player;
+mod weakness;
 
 // Flatten
 pub use ability::Ability;
-pub use attack::Attack;
+pub use ancient_trait::AncientTrait;
+pub use attack::{Attack, Damage, DamageModifier};
 pub use card_market::{CardMarket, CardMarketPrices};
 pub use card::Card;
+pub use card_number::CardNumber;
+pub use energy_type::EnergyType;
 pub use images::CardImages;
-pub use tcg_player::{TCGPlayer, TCGPlayerPrints, TCGPlayerPrice};
\ No newline at end of file
+pub use kind::{CardKind, EnergyCard, EnergyKind, PokemonCard, TrainerCard, TrainerKind};
+pub use legalities::{Format, Legalities, Legality};
+pub use rarity::Rarity;
+pub use subtype::{Stage, Subtype};
+pub use supertype::Supertype;
+pub use tcg_player::{TCGPlayer, TCGPlayerPrints, TCGPlayerPrice};
+pub use weakness::{Modifier, Resistance, Weakness};
\ No newline at end of file
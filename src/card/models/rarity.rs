@@ -0,0 +1,98 @@
+use crate::utils::string_enum;
+
+string_enum! {
+	/// The rarity of a card.
+	pub enum Rarity {
+		/// "ACE SPEC Rare"
+		AceSpecRare => "ACE SPEC Rare",
+		/// "Amazing Rare"
+		AmazingRare => "Amazing Rare",
+		/// "Classic Collection"
+		ClassicCollection => "Classic Collection",
+		/// "Common"
+		Common => "Common",
+		/// "Double Rare"
+		DoubleRare => "Double Rare",
+		/// "Hyper Rare"
+		HyperRare => "Hyper Rare",
+		/// "Illustration Rare"
+		IllustrationRare => "Illustration Rare",
+		/// "LEGEND"
+		Legend => "LEGEND",
+		/// "Promo"
+		Promo => "Promo",
+		/// "Radiant Rare"
+		RadiantRare => "Radiant Rare",
+		/// "Rare"
+		Rare => "Rare",
+		/// "Rare ACE"
+		RareAce => "Rare ACE",
+		/// "Rare BREAK"
+		RareBreak => "Rare BREAK",
+		/// "Rare Holo"
+		RareHolo => "Rare Holo",
+		/// "Rare Holo EX"
+		RareHoloEx => "Rare Holo EX",
+		/// "Rare Holo GX"
+		RareHoloGx => "Rare Holo GX",
+		/// "Rare Holo LV.X"
+		RareHoloLvX => "Rare Holo LV.X",
+		/// "Rare Holo Star"
+		RareHoloStar => "Rare Holo Star",
+		/// "Rare Holo V"
+		RareHoloV => "Rare Holo V",
+		/// "Rare Holo VMAX"
+		RareHoloVmax => "Rare Holo VMAX",
+		/// "Rare Holo VSTAR"
+		RareHoloVstar => "Rare Holo VSTAR",
+		/// "Rare Prime"
+		RarePrime => "Rare Prime",
+		/// "Rare Prism Star"
+		RarePrismStar => "Rare Prism Star",
+		/// "Rare Rainbow"
+		RareRainbow => "Rare Rainbow",
+		/// "Rare Secret"
+		RareSecret => "Rare Secret",
+		/// "Rare Shining"
+		RareShining => "Rare Shining",
+		/// "Rare Shiny"
+		RareShiny => "Rare Shiny",
+		/// "Rare Shiny GX"
+		RareShinyGx => "Rare Shiny GX",
+		/// "Rare Ultra"
+		RareUltra => "Rare Ultra",
+		/// "Shiny Rare"
+		ShinyRare => "Shiny Rare",
+		/// "Shiny Ultra Rare"
+		ShinyUltraRare => "Shiny Ultra Rare",
+		/// "Special Illustration Rare"
+		SpecialIllustrationRare => "Special Illustration Rare",
+		/// "Trainer Gallery Rare Holo"
+		TrainerGalleryRareHolo => "Trainer Gallery Rare Holo",
+		/// "Ultra Rare"
+		UltraRare => "Ultra Rare",
+		/// "Uncommon"
+		Uncommon => "Uncommon",
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_rarity_from_str() {
+		assert_eq!(Rarity::from("Rare Holo VSTAR"), Rarity::RareHoloVstar);
+		assert_eq!(Rarity::from("Mega Hyper Rare"), Rarity::Unknown(String::from("Mega Hyper Rare")));
+		assert_eq!(Rarity::RareHoloVstar.to_string(), "Rare Holo VSTAR");
+	}
+
+	#[test]
+	fn test_rarity_serde() -> serde_json::Result<()> {
+		let rarities: Vec<Rarity> = serde_json::from_str(r#"["ACE SPEC Rare", "Mega Hyper Rare"]"#)?;
+		assert_eq!(rarities, vec![Rarity::AceSpecRare, Rarity::Unknown(String::from("Mega Hyper Rare"))]);
+		assert_eq!(serde_json::to_string(&rarities)?, r#"["ACE SPEC Rare","Mega Hyper Rare"]"#);
+
+		Ok(())
+	}
+}
@@ -0,0 +1,134 @@
+use crate::utils::string_enum;
+
+string_enum! {
+	/// A subtype of a card, such as its evolution stage, mechanic or Trainer type.
+	pub enum Subtype {
+		/// "ACE SPEC"
+		AceSpec => "ACE SPEC",
+		/// "Ancient"
+		Ancient => "Ancient",
+		/// "Baby"
+		Baby => "Baby",
+		/// "Basic"
+		Basic => "Basic",
+		/// "BREAK"
+		Break => "BREAK",
+		/// "Eternamax"
+		Eternamax => "Eternamax",
+		/// "ex", the Pokémon ex of the EX and Scarlet & Violet series.
+		Ex => "ex",
+		/// "EX", the Pokémon-EX of the Black & White and XY series.
+		LegacyEx => "EX",
+		/// "Fusion Strike"
+		FusionStrike => "Fusion Strike",
+		/// "Future"
+		Future => "Future",
+		/// "Goldenrod Game Corner"
+		GoldenrodGameCorner => "Goldenrod Game Corner",
+		/// "GX"
+		Gx => "GX",
+		/// "Item"
+		Item => "Item",
+		/// "LEGEND"
+		Legend => "LEGEND",
+		/// "Level-Up"
+		LevelUp => "Level-Up",
+		/// "MEGA"
+		Mega => "MEGA",
+		/// "Pokémon Tool"
+		PokemonTool => "Pokémon Tool",
+		/// "Pokémon Tool F"
+		PokemonToolF => "Pokémon Tool F",
+		/// "Prism Star"
+		PrismStar => "Prism Star",
+		/// "Radiant"
+		Radiant => "Radiant",
+		/// "Rapid Strike"
+		RapidStrike => "Rapid Strike",
+		/// "Restored"
+		Restored => "Restored",
+		/// "Rocket's Secret Machine"
+		RocketsSecretMachine => "Rocket's Secret Machine",
+		/// "Single Strike"
+		SingleStrike => "Single Strike",
+		/// "SP"
+		Sp => "SP",
+		/// "Special"
+		Special => "Special",
+		/// "Stadium"
+		Stadium => "Stadium",
+		/// "Stage 1"
+		Stage1 => "Stage 1",
+		/// "Stage 2"
+		Stage2 => "Stage 2",
+		/// "Star"
+		Star => "Star",
+		/// "Supporter"
+		Supporter => "Supporter",
+		/// "TAG TEAM"
+		TagTeam => "TAG TEAM",
+		/// "Team Plasma"
+		TeamPlasma => "Team Plasma",
+		/// "Technical Machine"
+		TechnicalMachine => "Technical Machine",
+		/// "Tera"
+		Tera => "Tera",
+		/// "Ultra Beast"
+		UltraBeast => "Ultra Beast",
+		/// "V"
+		V => "V",
+		/// "V-UNION"
+		VUnion => "V-UNION",
+		/// "VMAX"
+		Vmax => "VMAX",
+		/// "VSTAR"
+		Vstar => "VSTAR",
+	}
+}
+
+/// How a Pokémon is put into play, from its subtypes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stage {
+	/// A Basic Pokémon.
+	Basic,
+	/// A Stage 1 Pokémon.
+	Stage1,
+	/// A Stage 2 Pokémon.
+	Stage2,
+	/// A Restored Pokémon, played from a Fossil card.
+	Restored,
+	/// A Pokémon BREAK.
+	Break,
+	/// A Pokémon LV.X.
+	LevelUp,
+	/// A Mega Evolution Pokémon-EX.
+	Mega,
+	/// A Pokémon VMAX.
+	Vmax,
+	/// A Pokémon VSTAR.
+	Vstar,
+	/// A Pokémon V-UNION.
+	VUnion,
+}
+
+impl Stage {
+	/// The stage of a card with `subtypes`, if it's a Pokémon.
+	pub(crate) fn from_subtypes(subtypes: &[Subtype]) -> Option<Stage> {
+		// Stages are checked before mechanics, since newer Mega Evolutions are Stage 1 or 2.
+		[
+			(Subtype::Stage2, Stage::Stage2),
+			(Subtype::Stage1, Stage::Stage1),
+			(Subtype::Vmax, Stage::Vmax),
+			(Subtype::Vstar, Stage::Vstar),
+			(Subtype::VUnion, Stage::VUnion),
+			(Subtype::Mega, Stage::Mega),
+			(Subtype::Break, Stage::Break),
+			(Subtype::LevelUp, Stage::LevelUp),
+			(Subtype::Restored, Stage::Restored),
+			(Subtype::Basic, Stage::Basic),
+		]
+		.into_iter()
+		.find(|(subtype, _)| subtypes.contains(subtype))
+		.map(|(_, stage)| stage)
+	}
+}
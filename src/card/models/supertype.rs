@@ -0,0 +1,35 @@
+use crate::utils::string_enum;
+
+string_enum! {
+	/// The supertype of a card.
+	pub enum Supertype {
+		/// A Pokémon card.
+		Pokemon => "Pokémon" | "Pokemon",
+		/// A Trainer card, such as an Item, Supporter or Stadium.
+		Trainer => "Trainer",
+		/// An Energy card.
+		Energy => "Energy",
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_supertype_from_str() {
+		assert_eq!(Supertype::from("Pokémon"), Supertype::Pokemon);
+		assert_eq!(Supertype::from("Pokemon"), Supertype::Pokemon);
+		assert_eq!(Supertype::from("Ticket"), Supertype::Unknown(String::from("Ticket")));
+		assert_eq!(Supertype::Pokemon.to_string(), "Pokémon");
+	}
+
+	#[test]
+	fn test_supertype_serde() -> serde_json::Result<()> {
+		let supertypes: Vec<Supertype> = serde_json::from_str(r#"["Pokemon", "Trainer", "Ticket"]"#)?;
+		assert_eq!(supertypes, vec![Supertype::Pokemon, Supertype::Trainer, Supertype::Unknown(String::from("Ticket"))]);
+		assert_eq!(serde_json::to_string(&supertypes)?, r#"["Pokémon","Trainer","Ticket"]"#);
+
+		Ok(())
+	}
+}
@@ -12,6 +12,14 @@ pub struct TCGPlayer {
 	pub prices: Option<TCGPlayerPrints>,
 }
 
+#[cfg(feature = "chrono")]
+impl TCGPlayer {
+	/// The date the prices were last updated.
+	pub fn updated(&self) -> Option<chrono::NaiveDate> {
+		crate::utils::parse_date(&self.updated_at)
+	}
+}
+
 /// Holds the TCG Player price data for each print style for a card.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
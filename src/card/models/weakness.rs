@@ -0,0 +1,206 @@
+use serde::{Deserialize, Serialize};
+
+use super::EnergyType;
+
+/// A weakness of a Pokémon card.
+/// 
+/// Serialized back to the value the API sent, such as "x2", unless the modifier is changed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "RawTypeModifier", into = "RawTypeModifier")]
+pub struct Weakness {
+	/// The energy type the Pokémon is weak to.
+	pub energy_type: EnergyType,
+	/// How damage from that type is changed, usually ×2.
+	pub modifier: Modifier,
+	/// The value from the API, if it isn't written the way `Modifier` displays.
+	value: Option<String>,
+}
+
+/// A resistance of a Pokémon card.
+/// 
+/// Serialized back to the value the API sent, such as "−30", unless the modifier is changed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(from = "RawTypeModifier", into = "RawTypeModifier")]
+pub struct Resistance {
+	/// The energy type the Pokémon resists.
+	pub energy_type: EnergyType,
+	/// How damage from that type is changed, usually -20 or -30.
+	pub modifier: Modifier,
+	/// The value from the API, if it isn't written the way `Modifier` displays.
+	value: Option<String>,
+}
+
+/// Implements the constructor, comparisons and serde conversions shared by `Weakness` and
+/// `Resistance`. Two of them are equal when their type and modifier are, however the
+/// value was written.
+macro_rules! type_modifier {
+	($name:ident) => {
+		impl $name {
+			#[doc = concat!("Returns a `", stringify!($name), "` for `energy_type` with `modifier`.")]
+			pub fn new(energy_type: EnergyType, modifier: Modifier) -> $name {
+				$name { energy_type, modifier, value: None }
+			}
+		}
+
+		impl PartialEq for $name {
+			fn eq(&self, other: &Self) -> bool {
+				(&self.energy_type, &self.modifier) == (&other.energy_type, &other.modifier)
+			}
+		}
+
+		impl Eq for $name {}
+
+		impl std::hash::Hash for $name {
+			fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+				self.energy_type.hash(state);
+				self.modifier.hash(state);
+			}
+		}
+
+		impl From<RawTypeModifier> for $name {
+			fn from(raw: RawTypeModifier) -> Self {
+				let modifier = Modifier::from(raw.value.as_str());
+				let value = (modifier.to_string() != raw.value).then_some(raw.value);
+				$name { energy_type: raw.energy_type, modifier, value }
+			}
+		}
+
+		impl From<$name> for RawTypeModifier {
+			fn from(item: $name) -> Self {
+				// The original value is only kept while it still parses to the same modifier.
+				let value = item.value
+					.filter(|value| Modifier::from(value.as_str()) == item.modifier)
+					.unwrap_or_else(|| item.modifier.to_string());
+				RawTypeModifier { energy_type: item.energy_type, value }
+			}
+		}
+	};
+}
+
+type_modifier!(Weakness);
+type_modifier!(Resistance);
+
+/// The shape of a weakness or resistance in the API.
+#[derive(Serialize, Deserialize)]
+struct RawTypeModifier {
+	#[serde(rename = "type")]
+	energy_type: EnergyType,
+	value: String,
+}
+
+/// How a weakness or resistance changes the damage a Pokémon takes.
+/// 
+/// Serialized as the API writes it, such as "×2", "+20" or "-30".
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Modifier {
+	/// The damage is multiplied, such as "×2".
+	Times(u32),
+	/// The damage is increased, such as "+20".
+	Plus(u32),
+	/// The damage is reduced, such as "-30".
+	Minus(u32),
+	/// A value that couldn't be parsed.
+	Unknown(String),
+}
+
+impl Modifier {
+	/// Returns `damage` after the modifier is applied.
+	pub fn apply(&self, damage: u32) -> u32 {
+		match self {
+			Modifier::Times(n) => damage.saturating_mul(*n),
+			Modifier::Plus(n) => damage.saturating_add(*n),
+			Modifier::Minus(n) => damage.saturating_sub(*n),
+			Modifier::Unknown(_) => damage,
+		}
+	}
+}
+
+impl From<&str> for Modifier {
+	fn from(value: &str) -> Self {
+		let trimmed = value.trim();
+		let mut chars = trimmed.chars();
+		let modifier: Option<fn(u32) -> Modifier> = match chars.next() {
+			Some('×' | 'x' | 'X' | '*') => Some(Modifier::Times),
+			Some('+') => Some(Modifier::Plus),
+			Some('-' | '−') => Some(Modifier::Minus),
+			_ => None,
+		};
+		match (modifier, chars.as_str().trim().parse()) {
+			(Some(modifier), Ok(n)) => modifier(n),
+			_ => Modifier::Unknown(value.to_string()),
+		}
+	}
+}
+
+impl std::str::FromStr for Modifier {
+	type Err = std::convert::Infallible;
+
+	fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+		Ok(Modifier::from(s))
+	}
+}
+
+impl core::fmt::Display for Modifier {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Modifier::Times(n) => write!(f, "×{n}"),
+			Modifier::Plus(n) => write!(f, "+{n}"),
+			Modifier::Minus(n) => write!(f, "-{n}"),
+			Modifier::Unknown(value) => f.write_str(value),
+		}
+	}
+}
+
+impl Serialize for Modifier {
+	fn serialize<S: serde::Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+		serializer.collect_str(self)
+	}
+}
+
+impl<'de> Deserialize<'de> for Modifier {
+	fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+		let value = Modifier::from(String::deserialize(deserializer)?.as_str());
+		if let Modifier::Unknown(value) = &value {
+			crate::client::unknown_value(value)?;
+		}
+		Ok(value)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_modifier_from_str() {
+		assert_eq!(Modifier::from("×2"), Modifier::Times(2));
+		assert_eq!(Modifier::from("+20"), Modifier::Plus(20));
+		assert_eq!(Modifier::from("-30"), Modifier::Minus(30));
+		assert_eq!(Modifier::from("×"), Modifier::Unknown(String::from("×")));
+		assert_eq!(Modifier::Times(2).apply(60), 120);
+		assert_eq!(Modifier::Minus(30).apply(20), 0);
+	}
+
+	#[test]
+	fn test_weakness_serde() -> serde_json::Result<()> {
+		let json = r#"{"type":"Fire","value":"×2"}"#;
+		let weakness: Weakness = serde_json::from_str(json)?;
+		assert_eq!(weakness, Weakness::new(EnergyType::Fire, Modifier::Times(2)));
+		assert_eq!(serde_json::to_string(&weakness)?, json);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_weakness_keeps_original_value() -> serde_json::Result<()> {
+		let json = r#"{"type":"Water","value":"x2"}"#;
+		let mut weakness: Weakness = serde_json::from_str(json)?;
+		assert_eq!(weakness, Weakness::new(EnergyType::Water, Modifier::Times(2)));
+		assert_eq!(serde_json::to_string(&weakness)?, json);
+
+		weakness.modifier = Modifier::Times(3);
+		assert_eq!(serde_json::to_string(&weakness)?, r#"{"type":"Water","value":"×3"}"#);
+
+		Ok(())
+	}
+}
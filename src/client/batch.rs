@@ -0,0 +1,97 @@
+use std::collections::{HashMap, HashSet};
+
+/// The longest a query built from a batch of ids may be once URL encoded, leaving room for
+/// the rest of the URL under the common limit of 2,048 characters.
+const MAX_QUERY_LEN: usize = 1500;
+
+/// The results of fetching a batch of items by id.
+///
+/// Returned by `Client::get_cards` and `Client::get_sets`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchResult<T> {
+	/// The items that were found, keyed by id.
+	pub found: HashMap<String, T>,
+	/// The ids that weren't found, in the order they were requested.
+	pub missing: Vec<String>,
+}
+
+impl<T> BatchResult<T> {
+	/// Sorts `items` by whether their id was requested.
+	pub(crate) fn new(ids: &[String], items: impl IntoIterator<Item = T>, id: impl Fn(&T) -> &str) -> BatchResult<T> {
+		let found: HashMap<String, T> = items.into_iter()
+			.map(|item| (id(&item).to_string(), item))
+			.collect();
+		let missing = ids.iter()
+			.filter(|i| !found.contains_key(*i))
+			.cloned()
+			.collect();
+
+		BatchResult { found, missing }
+	}
+}
+
+impl<T> Default for BatchResult<T> {
+	fn default() -> Self {
+		BatchResult { found: HashMap::new(), missing: vec![] }
+	}
+}
+
+/// Removes duplicate and empty ids, keeping the first of each.
+pub(crate) fn dedupe_ids(ids: impl IntoIterator<Item = impl Into<String>>) -> Vec<String> {
+	let mut seen = HashSet::new();
+	ids.into_iter()
+		.map(Into::into)
+		.filter(|id: &String| !id.is_empty() && seen.insert(id.clone()))
+		.collect()
+}
+
+/// Splits `ids` into `id:"a" OR id:"b" ...` queries short enough to send in a URL.
+pub(crate) fn id_queries(ids: &[String]) -> Vec<String> {
+	let mut queries: Vec<String> = vec![];
+	let mut current = String::new();
+	for id in ids {
+		let term = format!("id:\"{}\"", id.replace('"', ""));
+		let joined = if current.is_empty() { term.clone() } else { format!("{current} OR {term}") };
+		if !current.is_empty() && encoded_len(&joined) > MAX_QUERY_LEN {
+			queries.push(std::mem::replace(&mut current, term));
+		} else {
+			current = joined;
+		}
+	}
+	if !current.is_empty() {
+		queries.push(current);
+	}
+
+	queries
+}
+
+/// The length of `value` once it's percent encoded as a query parameter.
+fn encoded_len(value: &str) -> usize {
+	value.bytes()
+		.map(|b| if b.is_ascii_alphanumeric() || b"-._~ ".contains(&b) { 1 } else { 3 })
+		.sum()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_id_queries_split_long_batches() {
+		let ids: Vec<String> = (1..=200).map(|i| format!("sv3pt5-{i}")).collect();
+		let queries = id_queries(&ids);
+		assert!(queries.len() > 1);
+		assert!(queries.iter().all(|q| encoded_len(q) <= MAX_QUERY_LEN));
+		assert_eq!(queries.iter().map(|q| q.matches("id:").count()).sum::<usize>(), 200usize);
+		assert!(queries[0].starts_with("id:\"sv3pt5-1\" OR id:\"sv3pt5-2\""));
+	}
+
+	#[test]
+	fn test_batch_result_lists_missing_ids() {
+		let ids = dedupe_ids(["xy1-1", "xy1-2", "xy1-1", "", "bad-id"]);
+		assert_eq!(ids, vec!["xy1-1", "xy1-2", "bad-id"]);
+		let result = BatchResult::new(&ids, vec![String::from("xy1-2"), String::from("xy1-1")], |s| s.as_str());
+		assert_eq!(result.found.len(), 2usize);
+		assert_eq!(result.missing, vec![String::from("bad-id")]);
+	}
+}
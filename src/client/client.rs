@@ -1,10 +1,32 @@
+use std::num::NonZeroUsize;
 use std::sync::Arc;
-use crate::{Requestable, Result};
+use std::time::{Duration, Instant};
+
+use tokio::sync::Semaphore;
+
+use crate::{Error, Requestable, Result, SchemaDrift};
+use super::config::Config;
+use super::coalesce::{Fetched, InFlight, RequestKey};
+use super::limit::{ConcurrencyLimit, RateLimit};
+use super::metadata::{ClientStats, ResponseMetadata, StatsCounters};
+use super::middleware::{Middleware, MiddlewareChain};
+use super::preview::RequestPreview;
+use super::schema::{self, Strictness};
 
 /// A client for the Pokémon TCG IO REST API.
 #[derive(Debug, Clone)]
 pub struct Client {
-	inner: Arc<ClientInner>
+	inner: Arc<ClientInner>,
+	options: RequestOptions,
+}
+
+/// Settings that a builder can override for the requests it sends.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RequestOptions {
+	pub(crate) timeout: Option<Duration>,
+	pub(crate) deadline: Option<Duration>,
+	pub(crate) api_key: Option<String>,
+	pub(crate) limit: Option<Arc<Semaphore>>,
 }
 
 impl Default for Client {
@@ -24,12 +46,41 @@ impl Client {
 		ClientBuilder::default().api_key(key).build()
 	}
 
+	/// Returns a `Client` configured from environment variables.
+	/// 
+	/// See [`ClientBuilder::from_env`] for the variables that are read.
+	/// 
+	/// # Errors
+	/// 
+	/// This method fails with an `Error::ConfigError` if a variable has an invalid value.
+	pub fn from_env() -> Result<Client> {
+		Ok(ClientBuilder::from_env()?.build())
+	}
+
 	fn web_client(&self) -> &reqwest::Client {
 		&self.inner.web_client
 	}
 
-	fn api_key(&self) -> String {
-		self.inner.api_key.as_ref().unwrap_or(&String::from("")).to_string()
+	/// The API key to send, if any.
+	fn api_key(&self) -> Option<&str> {
+		self.options.api_key.as_deref()
+			.or(self.inner.api_key.as_deref())
+			.filter(|key| !key.is_empty())
+	}
+
+	/// The settings a builder can override for its own requests.
+	pub(crate) fn options_mut(&mut self) -> &mut RequestOptions {
+		&mut self.options
+	}
+
+	/// The longest a paginated request may take to fetch all of its pages.
+	pub(crate) fn deadline(&self) -> Option<Duration> {
+		self.options.deadline.or(self.inner.deadline)
+	}
+
+	/// Returns a snapshot of the requests sent by this client and its clones.
+	pub fn stats(&self) -> ClientStats {
+		self.inner.stats.snapshot()
 	}
 
 	pub(crate) async fn get<T, R>(&self, request: R) -> Result<T>
@@ -37,37 +88,332 @@ impl Client {
 		T: serde::de::DeserializeOwned,
 		R: Requestable,
 	{
-		let req = self.web_client()
-			.get(format!("https://api.pokemontcg.io/v2/{}", request.endpoint()))
-			.header("X-Api-Key", self.api_key())
-			.query(&request.params());
-		let ret: T = req.send().await?.json().await?;
+		let (ret, _) = self.get_with_metadata(request).await?;
 		Ok(ret)
 	}
+
+	pub(crate) async fn get_with_metadata<T, R>(&self, request: R) -> Result<(T, ResponseMetadata)>
+	where
+		T: serde::de::DeserializeOwned,
+		R: Requestable,
+	{
+		#[cfg(feature = "tracing")]
+		let span = request_span(&request);
+		let fut = self.execute(request);
+		#[cfg(feature = "tracing")]
+		let fut = tracing::Instrument::instrument(fut, span);
+		fut.await
+	}
+
+	async fn execute<T, R>(&self, request: R) -> Result<(T, ResponseMetadata)>
+	where
+		T: serde::de::DeserializeOwned,
+		R: Requestable,
+	{
+		let endpoint = request.endpoint();
+		let params = request.params();
+		let (body, metadata, shared) = if self.inner.coalesce {
+			let key = self.request_key(&endpoint, params);
+			let client = self.clone();
+			let fetch_key = key.clone();
+			let fetch = async move { client.fetch(&fetch_key.endpoint, &fetch_key.params).await };
+			// Requests only share a response when they have the same timeout, so a request that
+			// joined one already in flight waits no longer than it would have on its own.
+			let (ret, shared) = self.inner.in_flight.run(key, fetch).await;
+			if shared {
+				self.inner.stats.record_shared();
+			}
+			let (body, mut metadata) = ret?;
+			metadata.shared = shared;
+			(body, metadata, shared)
+		} else {
+			let (body, metadata) = self.fetch(&endpoint, &params).await?;
+			(body, metadata, false)
+		};
+		#[cfg(feature = "tracing")]
+		tracing::Span::current()
+			.record("status", metadata.status)
+			.record("latency_ms", metadata.latency.as_millis() as u64);
+		let ret = schema::decode(&endpoint, &body, &self.inner.strictness).inspect_err(|_| {
+			// Requests that shared the body would fail the same way, so only the one that sent it counts.
+			if !shared {
+				self.inner.stats.record_error();
+			}
+		})?;
+		Ok((ret, metadata))
+	}
+
+	/// Identifies the requests that can share a response with this one. Requests only share
+	/// a response if they would be sent with the same key, timeout and builder limit.
+	fn request_key(&self, endpoint: &str, params: Vec<(String, String)>) -> RequestKey {
+		RequestKey {
+			endpoint: endpoint.to_string(),
+			params,
+			api_key: self.api_key().map(String::from),
+			timeout: self.timeout(),
+			limit: self.options.limit.as_ref().map(|limit| Arc::as_ptr(limit) as usize),
+		}
+	}
+
+	/// The longest to wait for each request to complete.
+	fn timeout(&self) -> Option<Duration> {
+		self.options.timeout.or(self.inner.timeout)
+	}
+
+	/// Sends a request and reads the response body, retrying it if it fails, and counts it in
+	/// the client's stats.
+	///
+	/// Errors are counted here rather than by each caller, so a failed request that was
+	/// shared between several callers only counts once.
+	async fn fetch(&self, endpoint: &str, params: &[(String, String)]) -> Result<Fetched> {
+		let mut retries = 0;
+		let ret = loop {
+			let send = self.send(endpoint, params);
+			let ret = match self.timeout() {
+				Some(timeout) => tokio::time::timeout(timeout, send).await.unwrap_or(Err(Error::TimeoutError)),
+				None => send.await,
+			};
+			if retries == self.inner.max_retries {
+				break ret;
+			}
+			let Some(delay) = self.retry_delay(&ret, retries) else {
+				break ret;
+			};
+			tokio::time::sleep(delay).await;
+			retries += 1;
+			self.inner.stats.record_retry();
+		};
+		if ret.is_err() {
+			self.inner.stats.record_error();
+		}
+		ret
+	}
+
+	/// How long to wait before retrying a request that returned `ret`, or `None` if it
+	/// shouldn't be retried.
+	fn retry_delay(&self, ret: &Result<Fetched>, retries: u32) -> Option<Duration> {
+		let backoff = self.inner.retry_backoff.saturating_mul(2u32.saturating_pow(retries));
+		match ret {
+			Ok((_, metadata)) if metadata.status == 429 || metadata.status >= 500 => {
+				Some(metadata.retry_after.unwrap_or(backoff))
+			},
+			Err(Error::ApiResponseError(e)) if !e.is_builder() => Some(backoff),
+			Err(Error::TimeoutError) => Some(backoff),
+			_ => None,
+		}
+	}
+
+	/// Sends a request once and reads the response body.
+	async fn send(&self, endpoint: &str, params: &[(String, String)]) -> Result<Fetched> {
+		if let Some(rate_limit) = &self.inner.rate_limit {
+			rate_limit.acquire(self.api_key()).await;
+		}
+		// The semaphore is never closed, so acquiring a permit can't fail.
+		let _builder_permit = match &self.options.limit {
+			Some(limit) => Some(limit.clone().acquire_owned().await.expect("semaphore closed")),
+			None => None,
+		};
+		let _permit = self.inner.limit.acquire(self.api_key()).await;
+		self.inner.stats.record_request();
+		let mut req = self.build_request(endpoint, params)?;
+		self.inner.middleware.before_request(&mut req);
+		let started = Instant::now();
+		let res = self.web_client().execute(req).await?;
+		let elapsed = started.elapsed();
+		self.inner.middleware.after_response(&res, elapsed);
+		let mut metadata = ResponseMetadata::new(res.status().as_u16(), res.headers(), elapsed);
+		let body = res.bytes().await?;
+		metadata.bytes = body.len() as u64;
+		self.inner.stats.record_response(&metadata);
+		Ok((body, metadata))
+	}
+
+	/// Builds the request for `endpoint`, before any middleware runs.
+	fn build_request(&self, endpoint: &str, params: &[(String, String)]) -> Result<reqwest::Request> {
+		let mut req = self.web_client()
+			.get(format!("{}/{}", self.inner.base_url.trim_end_matches('/'), endpoint))
+			.query(params);
+		if let Some(key) = self.api_key() {
+			req = req.header("X-Api-Key", key);
+		}
+		if let Some(timeout) = self.timeout() {
+			req = req.timeout(timeout);
+		}
+
+		Ok(req.build()?)
+	}
+
+	/// Describes the request that would be sent for `request` without sending it.
+	pub(crate) fn preview<R: Requestable>(&self, request: &R) -> Result<RequestPreview> {
+		let req = self.build_request(&request.endpoint(), &request.params())?;
+		Ok(RequestPreview::from(&req))
+	}
+
+	/// Decodes a single value from a response the same way `get` decodes the whole body.
+	pub(crate) fn decode_value<T>(&self, endpoint: &str, value: serde_json::Value) -> Result<T>
+	where
+		T: serde::de::DeserializeOwned,
+	{
+		schema::decode_value(endpoint, value, &self.inner.strictness)
+	}
+}
+
+/// Creates the span for a request. The API key is sent as a header, so it never appears
+/// in the recorded params.
+#[cfg(feature = "tracing")]
+fn request_span<R: Requestable>(request: &R) -> tracing::Span {
+	let params = request.params();
+	let page = params.iter()
+		.find(|(name, _)| name == "page")
+		.and_then(|(_, value)| value.parse::<u32>().ok());
+	tracing::debug_span!(
+		"request",
+		endpoint = %request.endpoint(),
+		params = %params.iter().map(|(name, value)| format!("{name}={value}")).collect::<Vec<_>>().join("&"),
+		page,
+		status = tracing::field::Empty,
+		latency_ms = tracing::field::Empty,
+	)
 }
 
+/// The URL requests are sent to unless `ClientBuilder::base_url` is set.
+const DEFAULT_BASE_URL: &str = "https://api.pokemontcg.io/v2";
+
+/// How long to wait before the first retry unless `ClientBuilder::retry_backoff` is set.
+const DEFAULT_RETRY_BACKOFF: Duration = Duration::from_millis(500);
 
 /// ClientInner
 #[derive(Debug)]
 pub(super) struct ClientInner {
 	pub(super) web_client: reqwest::Client,
-	pub(super) api_key: Option<String>
+	pub(super) api_key: Option<String>,
+	pub(super) strictness: Strictness,
+	pub(super) middleware: MiddlewareChain,
+	pub(super) stats: StatsCounters,
+	pub(super) coalesce: bool,
+	pub(super) in_flight: InFlight,
+	pub(super) timeout: Option<Duration>,
+	pub(super) deadline: Option<Duration>,
+	pub(super) limit: ConcurrencyLimit,
+	pub(super) rate_limit: Option<RateLimit>,
+	pub(super) max_retries: u32,
+	pub(super) retry_backoff: Duration,
+	pub(super) base_url: String,
 }
 
 impl Default for ClientInner {
 	fn default() -> Self {
 		Self {
 			web_client: reqwest::Client::new(),
-			api_key: None
+			api_key: None,
+			strictness: Strictness::default(),
+			middleware: MiddlewareChain::default(),
+			stats: StatsCounters::default(),
+			coalesce: true,
+			in_flight: InFlight::default(),
+			timeout: None,
+			deadline: None,
+			limit: ConcurrencyLimit::default(),
+			rate_limit: None,
+			max_retries: 0,
+			retry_backoff: DEFAULT_RETRY_BACKOFF,
+			base_url: String::from(DEFAULT_BASE_URL),
 		}
 	}
 }
 
 /// A builder to construct the properties for a `Client`.
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct ClientBuilder {
 	web_client: Option<reqwest::Client>,
-	api_key: Option<String>
+	api_key: Option<String>,
+	strictness: Strictness,
+	middleware: MiddlewareChain,
+	coalesce: bool,
+	timeout: Option<Duration>,
+	deadline: Option<Duration>,
+	max_concurrent_requests: Option<NonZeroUsize>,
+	max_concurrent_requests_per_key: Option<NonZeroUsize>,
+	rate_limit: Option<(NonZeroUsize, Duration)>,
+	max_retries: u32,
+	retry_backoff: Duration,
+	base_url: Option<String>,
+}
+
+impl Default for ClientBuilder {
+	fn default() -> Self {
+		Self {
+			web_client: None,
+			api_key: None,
+			strictness: Strictness::default(),
+			middleware: MiddlewareChain::default(),
+			coalesce: true,
+			timeout: None,
+			deadline: None,
+			max_concurrent_requests: None,
+			max_concurrent_requests_per_key: None,
+			rate_limit: None,
+			max_retries: 0,
+			retry_backoff: DEFAULT_RETRY_BACKOFF,
+			base_url: None,
+		}
+	}
+}
+
+/// Constructors
+impl ClientBuilder {
+	/// Returns a `ClientBuilder` configured from environment variables.
+	/// 
+	/// The following variables are read, and any that aren't set are left at their defaults:
+	/// 
+	/// - `POKETCGAPIKEY` - the API key.
+	/// - `POKETCG_BASE_URL` - the URL requests are sent to.
+	/// - `POKETCG_TIMEOUT_MS` - the request timeout in milliseconds.
+	/// - `POKETCG_DEADLINE_MS` - the search deadline in milliseconds.
+	/// - `POKETCG_MAX_CONCURRENT_REQUESTS` - the most requests sent at once.
+	/// - `POKETCG_MAX_CONCURRENT_REQUESTS_PER_KEY` - the most requests sent at once with each API key.
+	/// - `POKETCG_COALESCE_REQUESTS` - `true` or `false`.
+	/// - `POKETCG_RATE_LIMIT_REQUESTS` and `POKETCG_RATE_LIMIT_PER_MS` - the most requests
+	///   sent with each API key in a window of milliseconds. Both must be set.
+	/// - `POKETCG_MAX_RETRIES` - how many times to retry a failed request.
+	/// - `POKETCG_RETRY_BACKOFF_MS` - how long to wait before the first retry in milliseconds.
+	/// 
+	/// The client doesn't cache responses yet, so there is no variable for a cache directory.
+	/// 
+	/// # Errors
+	/// 
+	/// This method fails with an `Error::ConfigError` naming the variable if one has an invalid value.
+	pub fn from_env() -> Result<ClientBuilder> {
+		Config::from_vars(|name| std::env::var(name).ok())?.apply(ClientBuilder::default())
+	}
+
+	/// Returns a `ClientBuilder` configured from a TOML file.
+	/// 
+	/// The file can contain the following keys, and any that are missing are left at their defaults:
+	/// 
+	/// ```toml
+	/// api_key = "YOUR_KEY"
+	/// base_url = "https://api.pokemontcg.io/v2"
+	/// timeout_ms = 10000
+	/// deadline_ms = 120000
+	/// max_concurrent_requests = 4
+	/// max_concurrent_requests_per_key = 2
+	/// rate_limit_requests = 1000
+	/// rate_limit_per_ms = 3600000
+	/// coalesce_requests = true
+	/// max_retries = 3
+	/// retry_backoff_ms = 500
+	/// ```
+	/// 
+	/// The client doesn't cache responses yet, so a cache directory is rejected as an unknown key.
+	/// 
+	/// # Errors
+	/// 
+	/// This method fails with an `Error::ConfigError` if the file can't be read, contains an
+	/// unknown key, or has an invalid value.
+	pub fn from_config(path: impl AsRef<std::path::Path>) -> Result<ClientBuilder> {
+		Config::from_file(path.as_ref())?.apply(ClientBuilder::default())
+	}
 }
 
 /// Builder methods
@@ -78,20 +424,301 @@ impl ClientBuilder {
 		self
 	}
 
+	/// The URL requests are sent to. Defaults to `https://api.pokemontcg.io/v2`.
+	pub fn base_url(mut self, url: impl Into<String>) -> Self {
+		self.base_url = Some(url.into());
+		self
+	}
+
 	/// Provide an API key to the client.
 	pub fn api_key(mut self, key: impl Into<String>) -> Self {
 		self.api_key = Some(key.into());
 		self
 	}
+
+	/// Fail requests whose responses don't match the models.
+	/// 
+	/// Unknown fields, type mismatches and values that decode into an enum's `Unknown`
+	/// variant are returned as an `Error::SchemaDriftError` listing every difference found
+	/// in the response.
+	pub fn strict(mut self) -> Self {
+		self.strictness = Strictness::Error;
+		self
+	}
+
+	/// Report responses that don't match the models to a callback.
+	/// 
+	/// Unknown fields and values are passed to `callback` and the response is still decoded. Type
+	/// mismatches are passed to `callback` before the request fails as usual.
+	pub fn on_schema_drift(mut self, callback: impl Fn(&SchemaDrift) + Send + Sync + 'static) -> Self {
+		self.strictness = Strictness::Warn(Arc::new(callback));
+		self
+	}
+
+	/// Add a [`Middleware`] that runs around every request the client sends.
+	/// 
+	/// Middleware runs in the order it was added.
+	pub fn middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+		self.middleware.push(middleware);
+		self
+	}
+
+	/// Whether identical requests that are in flight at the same time share one response.
+	/// 
+	/// Requests are identical when they have the same endpoint, parameters and API key. Enabled by default.
+	pub fn coalesce_requests(mut self, value: bool) -> Self {
+		self.coalesce = value;
+		self
+	}
+
+	/// The longest to wait for each request to complete.
+	/// 
+	/// A request that times out fails with an `Error::TimeoutError`, or with an
+	/// `Error::ApiResponseError` for which `is_timeout()` is true, unless it's retried.
+	/// Builders can override this with their own `timeout`.
+	pub fn timeout(mut self, value: Duration) -> Self {
+		self.timeout = Some(value);
+		self
+	}
+
+	/// The longest a search may take to fetch all of its pages.
+	/// 
+	/// Once the deadline passes, the search fails with an `Error::DeadlineExceededError`.
+	/// Builders can override this with their own `deadline`.
+	pub fn deadline(mut self, value: Duration) -> Self {
+		self.deadline = Some(value);
+		self
+	}
+
+	/// The most requests the client sends at once, across every API key. Further requests
+	/// wait for one to finish.
+	/// 
+	/// The batch builders can set a lower limit for their own requests with their own
+	/// `max_concurrent_requests`.
+	pub fn max_concurrent_requests(mut self, value: NonZeroUsize) -> Self {
+		self.max_concurrent_requests = Some(value);
+		self
+	}
+
+	/// The most requests the client sends at once with each API key, so one key can't use
+	/// every slot allowed by `max_concurrent_requests`. Further requests with the same key
+	/// wait for one to finish.
+	pub fn max_concurrent_requests_per_key(mut self, value: NonZeroUsize) -> Self {
+		self.max_concurrent_requests_per_key = Some(value);
+		self
+	}
+
+	/// The most requests the client sends with each API key in any window of `per`. Further
+	/// requests with the same key wait until they fit in the window.
+	/// 
+	/// Retries count towards the limit.
+	pub fn rate_limit(mut self, requests: NonZeroUsize, per: Duration) -> Self {
+		self.rate_limit = Some((requests, per));
+		self
+	}
+
+	/// How many times to retry a request that failed to send, timed out, or got a 429 or
+	/// 5xx response. Defaults to 0.
+	/// 
+	/// Each retry waits twice as long as the one before, starting at `retry_backoff`, unless
+	/// the response has a `Retry-After` header. Each attempt gets the full `timeout`.
+	pub fn max_retries(mut self, value: u32) -> Self {
+		self.max_retries = value;
+		self
+	}
+
+	/// How long to wait before the first retry. Defaults to 500 milliseconds.
+	pub fn retry_backoff(mut self, value: Duration) -> Self {
+		self.retry_backoff = value;
+		self
+	}
 }
 
 impl ClientBuilder {
 	/// Build the `Client` struct with the builder's configuration.
 	pub fn build(self) -> Client {
 		let inner = ClientInner {
-			web_client: self.web_client.unwrap_or(reqwest::Client::new()),
-			api_key: self.api_key
+			web_client: self.web_client.unwrap_or_default(),
+			api_key: self.api_key,
+			strictness: self.strictness,
+			middleware: self.middleware,
+			stats: StatsCounters::default(),
+			coalesce: self.coalesce,
+			in_flight: InFlight::default(),
+			timeout: self.timeout,
+			deadline: self.deadline,
+			limit: ConcurrencyLimit::new(
+				self.max_concurrent_requests.map(NonZeroUsize::get),
+				self.max_concurrent_requests_per_key.map(NonZeroUsize::get),
+			),
+			rate_limit: self.rate_limit.map(|(requests, per)| RateLimit::new(requests.get(), per)),
+			max_retries: self.max_retries,
+			retry_backoff: self.retry_backoff,
+			base_url: self.base_url.unwrap_or_else(|| String::from(DEFAULT_BASE_URL)),
 		};
-		Client { inner: Arc::new(inner) }
+		Client { inner: Arc::new(inner), options: RequestOptions::default() }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::future::IntoFuture;
+
+	use super::*;
+
+	fn api_key_header(client: &Client) -> Result<Option<String>> {
+		let req = client.build_request("cards", &[])?;
+		Ok(req.headers().get("X-Api-Key").map(|v| v.to_str().unwrap().to_string()))
+	}
+
+	#[test]
+	fn test_api_key_header() -> Result<()> {
+		assert_eq!(api_key_header(&Client::with_api_key("key"))?, Some(String::from("key")));
+		assert_eq!(api_key_header(&Client::default())?, None);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_empty_api_key_is_not_sent() -> Result<()> {
+		assert_eq!(api_key_header(&Client::with_api_key(""))?, None);
+
+		Ok(())
+	}
+
+	/// Returns a client for a server that accepts connections but never responds.
+	async fn unresponsive_client() -> Client {
+		let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let addr = listener.local_addr().unwrap();
+		tokio::spawn(async move {
+			let mut sockets = vec![];
+			while let Ok((socket, _)) = listener.accept().await {
+				sockets.push(socket);
+			}
+		});
+		Client::builder().base_url(format!("http://{addr}")).build()
+	}
+
+	fn is_timeout(e: &Error) -> bool {
+		match e {
+			Error::TimeoutError => true,
+			Error::ApiResponseError(e) => e.is_timeout(),
+			_ => false,
+		}
+	}
+
+	/// Returns a client for a server that closes each connection after `delay` without responding.
+	async fn closing_client(delay: Duration) -> Client {
+		let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let addr = listener.local_addr().unwrap();
+		tokio::spawn(async move {
+			while let Ok((socket, _)) = listener.accept().await {
+				tokio::spawn(async move {
+					tokio::time::sleep(delay).await;
+					drop(socket);
+				});
+			}
+		});
+		Client::builder().base_url(format!("http://{addr}")).build()
+	}
+
+	#[test]
+	fn test_request_key_includes_options() {
+		let client = Client::default();
+		let mut short = client.clone();
+		short.options_mut().timeout = Some(Duration::from_millis(50));
+		let mut limited = client.clone();
+		limited.options_mut().limit = Some(Arc::new(Semaphore::new(1)));
+		let key = |client: &Client| client.request_key("cards/sv1-1", vec![]);
+		assert_ne!(key(&client), key(&short));
+		assert_ne!(key(&client), key(&limited));
+		assert_eq!(key(&limited), key(&limited.clone()));
+	}
+
+	#[tokio::test]
+	async fn test_requests_with_different_timeouts_are_not_shared() {
+		let client = unresponsive_client().await;
+		let short = client.get_card("sv1-1").timeout(Duration::from_millis(50));
+		let long = client.get_card("sv1-1").timeout(Duration::from_secs(30));
+		let (short, long) = tokio::join!(
+			short.into_future(),
+			tokio::time::timeout(Duration::from_millis(300), long.into_future()),
+		);
+		assert!(matches!(short, Err(e) if is_timeout(&e)));
+		// The long request is still waiting instead of failing with the short one.
+		assert!(long.is_err());
+	}
+
+	#[tokio::test]
+	async fn test_shared_failure_counts_one_error() {
+		let client = closing_client(Duration::from_millis(100)).await;
+		let (a, b) = tokio::join!(
+			client.get_card("sv1-1").into_future(),
+			client.get_card("sv1-1").into_future(),
+		);
+		assert!(a.is_err() && b.is_err());
+		let stats = client.stats();
+		assert_eq!((stats.requests, stats.errors, stats.shared), (1, 1, 1));
+	}
+
+	#[tokio::test]
+	async fn test_timeout_counts_as_error() {
+		let client = unresponsive_client().await;
+		let ret = client.get_card("sv1-1").timeout(Duration::from_millis(50)).await;
+		assert!(matches!(ret, Err(e) if is_timeout(&e)));
+		assert_eq!(client.stats().errors, 1);
+	}
+
+	/// Returns a client builder for a server that sends each of `responses` in turn, one per connection.
+	async fn scripted_client(responses: &[&'static str]) -> ClientBuilder {
+		use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+		let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let addr = listener.local_addr().unwrap();
+		let responses = responses.to_vec();
+		tokio::spawn(async move {
+			for response in responses {
+				let Ok((mut socket, _)) = listener.accept().await else {
+					return;
+				};
+				let mut buf = [0; 4096];
+				let _ = socket.read(&mut buf).await;
+				let _ = socket.write_all(response.as_bytes()).await;
+			}
+		});
+		Client::builder()
+			.base_url(format!("http://{addr}"))
+			.with_reqwest_client(reqwest::Client::builder().pool_max_idle_per_host(0).build().unwrap())
+			.retry_backoff(Duration::from_millis(1))
+	}
+
+	const UNAVAILABLE: &str = "HTTP/1.1 503 Service Unavailable\r\ncontent-length: 0\r\nconnection: close\r\n\r\n";
+	const CARD: &str = "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: 25\r\nconnection: close\r\n\r\n{\"data\": {\"id\": \"sv1-1\"}}";
+
+	#[tokio::test]
+	async fn test_failed_requests_are_retried() -> Result<()> {
+		let client = scripted_client(&[UNAVAILABLE, CARD]).await.max_retries(1).build();
+		let card = client.get_card("sv1-1").await?;
+		assert_eq!(card.map(|card| card.id), Some(String::from("sv1-1")));
+		let stats = client.stats();
+		assert_eq!((stats.requests, stats.retries, stats.errors), (2, 1, 0));
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_requests_are_not_retried_by_default() {
+		let client = scripted_client(&[UNAVAILABLE, CARD]).await.build();
+		assert!(client.get_card("sv1-1").await.is_err());
+		assert_eq!(client.stats().retries, 0);
 	}
-}
\ No newline at end of file
+
+	#[test]
+	fn test_request_api_key_overrides_client_key() -> Result<()> {
+		let mut client = Client::with_api_key("client");
+		client.options_mut().api_key = Some(String::from("request"));
+		assert_eq!(api_key_header(&client)?, Some(String::from("request")));
+
+		Ok(())
+	}
+}
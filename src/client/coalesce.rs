@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use bytes::Bytes;
+use futures::future::{BoxFuture, Shared};
+use futures::{FutureExt, TryFutureExt};
+
+use crate::{Error, Result};
+use super::ResponseMetadata;
+
+/// A response body that has been received but not yet decoded.
+pub(crate) type Fetched = (Bytes, ResponseMetadata);
+
+type SharedFetch = Shared<BoxFuture<'static, core::result::Result<Fetched, Arc<Error>>>>;
+
+/// Identifies requests that would receive the same response.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct RequestKey {
+	pub(crate) endpoint: String,
+	pub(crate) params: Vec<(String, String)>,
+	pub(crate) api_key: Option<String>,
+	pub(crate) timeout: Option<Duration>,
+	/// The address of the builder's concurrency limit, if it set one.
+	pub(crate) limit: Option<usize>,
+}
+
+/// The requests a `Client` is currently waiting on, so identical ones can share a response.
+#[derive(Default)]
+pub(crate) struct InFlight(Mutex<HashMap<RequestKey, SharedFetch>>);
+
+impl InFlight {
+	/// Runs `fetch`, or waits on an identical request that is already in flight instead.
+	///
+	/// Also returns whether the response was shared with an earlier request.
+	pub(crate) async fn run<F>(&self, key: RequestKey, fetch: F) -> (Result<Fetched>, bool)
+	where
+		F: Future<Output = Result<Fetched>> + Send + 'static,
+	{
+		let (fut, shared) = {
+			let mut in_flight = self.0.lock().unwrap();
+			match in_flight.get(&key) {
+				Some(fut) => (fut.clone(), true),
+				None => {
+					let fut = fetch.map_err(Arc::new).boxed().shared();
+					in_flight.insert(key.clone(), fut.clone());
+					(fut, false)
+				}
+			}
+		};
+
+		let mut guard = Waiter { in_flight: self, key, fut, done: false };
+		let ret = guard.fut.clone().await;
+		guard.done = true;
+		drop(guard);
+
+		// The error can only be unwrapped once nothing else is waiting on it.
+		let ret = ret.map_err(|e| Arc::try_unwrap(e).unwrap_or_else(Error::SharedRequestError));
+		(ret, shared)
+	}
+}
+
+/// Removes a request's entry from `InFlight` once it has completed, or once its last waiter
+/// is dropped without completing, so cancelled requests don't leave stale entries behind.
+struct Waiter<'a> {
+	in_flight: &'a InFlight,
+	key: RequestKey,
+	fut: SharedFetch,
+	done: bool,
+}
+
+impl Drop for Waiter<'_> {
+	fn drop(&mut self) {
+		let mut in_flight = self.in_flight.0.lock().unwrap();
+		if !in_flight.get(&self.key).is_some_and(|f| f.ptr_eq(&self.fut)) {
+			return;
+		}
+		// When cancelled, the map and this waiter each hold one handle if no one else is waiting.
+		if self.done || self.fut.strong_count().is_some_and(|n| n <= 2) {
+			in_flight.remove(&self.key);
+		}
+	}
+}
+
+impl core::fmt::Debug for InFlight {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let len = self.0.lock().map(|m| m.len()).unwrap_or_default();
+		write!(f, "InFlight({len})")
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::atomic::{AtomicU32, Ordering};
+
+	use reqwest::header::HeaderMap;
+
+	use super::*;
+
+	fn key(id: &str) -> RequestKey {
+		RequestKey { endpoint: format!("cards/{id}"), params: vec![], api_key: None, timeout: None, limit: None }
+	}
+
+	async fn fetch(calls: Arc<AtomicU32>) -> Result<Fetched> {
+		calls.fetch_add(1, Ordering::SeqCst);
+		tokio::time::sleep(Duration::from_millis(20)).await;
+		let metadata = ResponseMetadata::new(200, &HeaderMap::new(), Duration::ZERO);
+		Ok((Bytes::from_static(b"{}"), metadata))
+	}
+
+	#[tokio::test]
+	async fn test_identical_requests_share_a_fetch() {
+		let in_flight = InFlight::default();
+		let calls = Arc::new(AtomicU32::new(0));
+		let (a, b) = tokio::join!(
+			in_flight.run(key("sv1-1"), fetch(calls.clone())),
+			in_flight.run(key("sv1-1"), fetch(calls.clone())),
+		);
+		assert!(a.0.is_ok() && b.0.is_ok());
+		assert_eq!(calls.load(Ordering::SeqCst), 1);
+		assert_eq!((a.1, b.1), (false, true));
+		assert_eq!(in_flight.0.lock().unwrap().len(), 0usize);
+	}
+
+	#[tokio::test]
+	async fn test_different_requests_fetch_separately() {
+		let in_flight = InFlight::default();
+		let calls = Arc::new(AtomicU32::new(0));
+		let (a, b) = tokio::join!(
+			in_flight.run(key("sv1-1"), fetch(calls.clone())),
+			in_flight.run(key("sv1-2"), fetch(calls.clone())),
+		);
+		assert!(!a.1 && !b.1);
+		assert_eq!(calls.load(Ordering::SeqCst), 2);
+	}
+
+	#[tokio::test]
+	async fn test_cancelled_request_is_removed() {
+		let in_flight = InFlight::default();
+		let calls = Arc::new(AtomicU32::new(0));
+		let ret = tokio::time::timeout(Duration::from_millis(5), in_flight.run(key("sv1-1"), fetch(calls.clone()))).await;
+		assert!(ret.is_err());
+		assert_eq!(in_flight.0.lock().unwrap().len(), 0usize);
+	}
+
+	#[tokio::test]
+	async fn test_cancelled_request_is_kept_for_other_waiters() {
+		let in_flight = InFlight::default();
+		let calls = Arc::new(AtomicU32::new(0));
+		let (a, b) = tokio::join!(
+			tokio::time::timeout(Duration::from_millis(5), in_flight.run(key("sv1-1"), fetch(calls.clone()))),
+			in_flight.run(key("sv1-1"), fetch(calls.clone())),
+		);
+		assert!(a.is_err());
+		assert!(b.0.is_ok() && b.1);
+		assert_eq!(calls.load(Ordering::SeqCst), 1);
+		assert_eq!(in_flight.0.lock().unwrap().len(), 0usize);
+	}
+}
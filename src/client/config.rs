@@ -0,0 +1,207 @@
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::{ClientBuilder, Error, Result};
+
+/// A problem with the configuration read by `Client::from_env` or `ClientBuilder::from_config`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigError {
+	/// The environment variable or config key that was invalid, or the path of a config
+	/// file that couldn't be read.
+	pub key: String,
+	/// What was wrong with it.
+	pub reason: String,
+}
+
+impl ConfigError {
+	fn new(key: impl Into<String>, reason: impl Into<String>) -> ConfigError {
+		ConfigError { key: key.into(), reason: reason.into() }
+	}
+}
+
+impl core::fmt::Display for ConfigError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "invalid `{}`: {}", self.key, self.reason)
+	}
+}
+
+impl From<ConfigError> for Error {
+	fn from(value: ConfigError) -> Self {
+		Error::ConfigError(value)
+	}
+}
+
+/// The environment variable holding the API key.
+pub(crate) const API_KEY_VAR: &str = "POKETCGAPIKEY";
+
+/// The settings that can be read from the environment or a config file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct Config {
+	api_key: Option<String>,
+	base_url: Option<String>,
+	timeout_ms: Option<u64>,
+	deadline_ms: Option<u64>,
+	max_concurrent_requests: Option<usize>,
+	max_concurrent_requests_per_key: Option<usize>,
+	rate_limit_requests: Option<usize>,
+	rate_limit_per_ms: Option<u64>,
+	coalesce_requests: Option<bool>,
+	max_retries: Option<u32>,
+	retry_backoff_ms: Option<u64>,
+}
+
+impl Config {
+	/// Reads the config from environment variables, using `var` to look each one up.
+	pub(crate) fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Config> {
+		fn parse<T: std::str::FromStr>(name: &str, value: Option<String>) -> Result<Option<T>> {
+			value.map(|v| v.trim().parse().map_err(|_| ConfigError::new(name, format!("`{v}` isn't a valid value")).into()))
+				.transpose()
+		}
+
+		Ok(Config {
+			api_key: var(API_KEY_VAR),
+			base_url: var("POKETCG_BASE_URL"),
+			timeout_ms: parse("POKETCG_TIMEOUT_MS", var("POKETCG_TIMEOUT_MS"))?,
+			deadline_ms: parse("POKETCG_DEADLINE_MS", var("POKETCG_DEADLINE_MS"))?,
+			max_concurrent_requests: parse("POKETCG_MAX_CONCURRENT_REQUESTS", var("POKETCG_MAX_CONCURRENT_REQUESTS"))?,
+			max_concurrent_requests_per_key: parse("POKETCG_MAX_CONCURRENT_REQUESTS_PER_KEY", var("POKETCG_MAX_CONCURRENT_REQUESTS_PER_KEY"))?,
+			rate_limit_requests: parse("POKETCG_RATE_LIMIT_REQUESTS", var("POKETCG_RATE_LIMIT_REQUESTS"))?,
+			rate_limit_per_ms: parse("POKETCG_RATE_LIMIT_PER_MS", var("POKETCG_RATE_LIMIT_PER_MS"))?,
+			coalesce_requests: parse("POKETCG_COALESCE_REQUESTS", var("POKETCG_COALESCE_REQUESTS"))?,
+			max_retries: parse("POKETCG_MAX_RETRIES", var("POKETCG_MAX_RETRIES"))?,
+			retry_backoff_ms: parse("POKETCG_RETRY_BACKOFF_MS", var("POKETCG_RETRY_BACKOFF_MS"))?,
+		})
+	}
+
+	/// Reads the config from a TOML file.
+	pub(crate) fn from_file(path: &Path) -> Result<Config> {
+		let contents = std::fs::read_to_string(path)
+			.map_err(|e| ConfigError::new(path.display().to_string(), e.to_string()))?;
+		Config::from_toml(&contents, &path.display().to_string())
+	}
+
+	fn from_toml(contents: &str, source: &str) -> Result<Config> {
+		Ok(toml::from_str(contents).map_err(|e| ConfigError::new(source, e.message()))?)
+	}
+
+	/// Applies the config on top of `builder`.
+	pub(crate) fn apply(self, mut builder: ClientBuilder) -> Result<ClientBuilder> {
+		if let Some(key) = self.api_key.filter(|key| !key.is_empty()) {
+			builder = builder.api_key(key);
+		}
+		if let Some(url) = self.base_url {
+			reqwest::Url::parse(&url).map_err(|e| ConfigError::new("base_url", e.to_string()))?;
+			builder = builder.base_url(url);
+		}
+		if let Some(ms) = self.timeout_ms {
+			builder = builder.timeout(Duration::from_millis(ms));
+		}
+		if let Some(ms) = self.deadline_ms {
+			builder = builder.deadline(Duration::from_millis(ms));
+		}
+		if let Some(max) = self.max_concurrent_requests {
+			let max = NonZeroUsize::new(max)
+				.ok_or_else(|| ConfigError::new("max_concurrent_requests", "must be at least 1"))?;
+			builder = builder.max_concurrent_requests(max);
+		}
+		if let Some(max) = self.max_concurrent_requests_per_key {
+			let max = NonZeroUsize::new(max)
+				.ok_or_else(|| ConfigError::new("max_concurrent_requests_per_key", "must be at least 1"))?;
+			builder = builder.max_concurrent_requests_per_key(max);
+		}
+		match (self.rate_limit_requests, self.rate_limit_per_ms) {
+			(Some(requests), Some(ms)) => {
+				let requests = NonZeroUsize::new(requests)
+					.ok_or_else(|| ConfigError::new("rate_limit_requests", "must be at least 1"))?;
+				builder = builder.rate_limit(requests, Duration::from_millis(ms));
+			},
+			(Some(_), None) => return Err(ConfigError::new("rate_limit_per_ms", "must be set with rate_limit_requests").into()),
+			(None, Some(_)) => return Err(ConfigError::new("rate_limit_requests", "must be set with rate_limit_per_ms").into()),
+			(None, None) => {}
+		}
+		if let Some(coalesce) = self.coalesce_requests {
+			builder = builder.coalesce_requests(coalesce);
+		}
+		if let Some(retries) = self.max_retries {
+			builder = builder.max_retries(retries);
+		}
+		if let Some(ms) = self.retry_backoff_ms {
+			builder = builder.retry_backoff(Duration::from_millis(ms));
+		}
+
+		Ok(builder)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::HashMap;
+
+	use super::*;
+
+	fn vars(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+		let vars: HashMap<String, String> = pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+		move |name| vars.get(name).cloned()
+	}
+
+	#[test]
+	fn test_config_from_vars() -> Result<()> {
+		let config = Config::from_vars(vars(&[(API_KEY_VAR, "key"), ("POKETCG_TIMEOUT_MS", "5000")]))?;
+		assert_eq!(config.api_key, Some(String::from("key")));
+		assert_eq!(config.timeout_ms, Some(5000));
+		assert_eq!(config.deadline_ms, None);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_config_from_vars_with_invalid_value() {
+		let ret = Config::from_vars(vars(&[("POKETCG_TIMEOUT_MS", "5s")]));
+		match ret {
+			Err(Error::ConfigError(e)) => assert_eq!(e.key, String::from("POKETCG_TIMEOUT_MS")),
+			_ => panic!("expected a ConfigError"),
+		}
+	}
+
+	#[test]
+	fn test_config_from_toml() -> Result<()> {
+		let config = Config::from_toml("api_key = \"key\"\nmax_concurrent_requests = 4\nmax_retries = 3\n", "test.toml")?;
+		assert_eq!(config.api_key, Some(String::from("key")));
+		assert_eq!(config.max_concurrent_requests, Some(4));
+		assert_eq!(config.max_retries, Some(3));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_config_from_toml_with_unknown_key() {
+		assert!(Config::from_toml("cache_dir = \"/tmp\"\n", "test.toml").is_err());
+	}
+
+	#[test]
+	fn test_config_ignores_empty_api_key() -> Result<()> {
+		let config = Config::from_vars(vars(&[(API_KEY_VAR, "")]))?;
+		let request = config.apply(ClientBuilder::default())?.build().get_card("xy1-1").to_request()?;
+		assert!(request.headers.iter().all(|(name, _)| !name.eq_ignore_ascii_case("x-api-key")));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_config_rejects_invalid_values() {
+		let config = Config { base_url: Some(String::from("not a url")), ..Default::default() };
+		assert!(matches!(config.apply(ClientBuilder::default()), Err(Error::ConfigError(_))));
+		let config = Config { max_concurrent_requests: Some(0), ..Default::default() };
+		assert!(matches!(config.apply(ClientBuilder::default()), Err(Error::ConfigError(_))));
+		let config = Config { max_concurrent_requests_per_key: Some(0), ..Default::default() };
+		assert!(matches!(config.apply(ClientBuilder::default()), Err(Error::ConfigError(_))));
+		let config = Config { rate_limit_requests: Some(0), rate_limit_per_ms: Some(1000), ..Default::default() };
+		assert!(matches!(config.apply(ClientBuilder::default()), Err(Error::ConfigError(_))));
+		let config = Config { rate_limit_requests: Some(10), ..Default::default() };
+		assert!(matches!(config.apply(ClientBuilder::default()), Err(Error::ConfigError(e)) if e.key == "rate_limit_per_ms"));
+	}
+}
@@ -0,0 +1,161 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Caps the number of requests in flight at once, in total and for each API key.
+#[derive(Debug, Default)]
+pub(crate) struct ConcurrencyLimit {
+	global: Option<Arc<Semaphore>>,
+	max_per_key: Option<usize>,
+	keys: Mutex<HashMap<Option<String>, Arc<Semaphore>>>,
+}
+
+impl ConcurrencyLimit {
+	pub(crate) fn new(max: Option<usize>, max_per_key: Option<usize>) -> ConcurrencyLimit {
+		ConcurrencyLimit {
+			global: max.map(|max| Arc::new(Semaphore::new(max))),
+			max_per_key,
+			keys: Mutex::default(),
+		}
+	}
+
+	/// Waits until a request can be sent with `api_key`. The slot is freed when the permit is dropped.
+	pub(crate) async fn acquire(&self, api_key: Option<&str>) -> Permit<'_> {
+		let mut permit = Permit { limit: self, key: None, global: None };
+		// The semaphores are never closed, so acquiring a permit can't fail.
+		if let Some(max) = self.max_per_key {
+			let key = api_key.map(String::from);
+			let semaphore = self.keys.lock().unwrap()
+				.entry(key.clone())
+				.or_insert_with(|| Arc::new(Semaphore::new(max)))
+				.clone();
+			let acquired = semaphore.clone().acquire_owned().await.expect("semaphore closed");
+			permit.key = Some((key, semaphore, acquired));
+		}
+		if let Some(global) = &self.global {
+			permit.global = Some(global.clone().acquire_owned().await.expect("semaphore closed"));
+		}
+
+		permit
+	}
+}
+
+/// A slot to send one request. Removes the API key's semaphore once nothing else holds it.
+pub(crate) struct Permit<'a> {
+	limit: &'a ConcurrencyLimit,
+	key: Option<(Option<String>, Arc<Semaphore>, OwnedSemaphorePermit)>,
+	global: Option<OwnedSemaphorePermit>,
+}
+
+impl Drop for Permit<'_> {
+	fn drop(&mut self) {
+		self.global.take();
+		let Some((key, semaphore, permit)) = self.key.take() else {
+			return;
+		};
+		drop(permit);
+		let mut keys = self.limit.keys.lock().unwrap();
+		// The map and this permit hold the only handles, so no other request has or is waiting on a slot.
+		if Arc::strong_count(&semaphore) == 2 && keys.get(&key).is_some_and(|s| Arc::ptr_eq(s, &semaphore)) {
+			keys.remove(&key);
+		}
+	}
+}
+
+/// Caps the number of requests sent with each API key in any window of time.
+#[derive(Debug)]
+pub(crate) struct RateLimit {
+	max: usize,
+	per: Duration,
+	sent: Mutex<HashMap<Option<String>, VecDeque<Instant>>>,
+}
+
+impl RateLimit {
+	pub(crate) fn new(max: usize, per: Duration) -> RateLimit {
+		RateLimit { max, per, sent: Mutex::default() }
+	}
+
+	/// Waits until a request can be sent with `api_key` without going over the limit, and
+	/// counts it as sent.
+	pub(crate) async fn acquire(&self, api_key: Option<&str>) {
+		loop {
+			let wait = {
+				let mut sent = self.sent.lock().unwrap();
+				let now = Instant::now();
+				// Forget the requests that have left the window, and the keys with none left.
+				sent.retain(|_, times| {
+					while times.front().is_some_and(|sent| now.duration_since(*sent) >= self.per) {
+						times.pop_front();
+					}
+					!times.is_empty()
+				});
+				let times = sent.entry(api_key.map(String::from)).or_default();
+				match times.front() {
+					Some(oldest) if times.len() >= self.max => self.per - now.duration_since(*oldest),
+					_ => {
+						times.push_back(now);
+						return;
+					}
+				}
+			};
+			tokio::time::sleep(wait).await;
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[tokio::test]
+	async fn test_limit_is_per_key() {
+		let limit = ConcurrencyLimit::new(None, Some(1));
+		let _a = limit.acquire(Some("a")).await;
+		let b = tokio::time::timeout(Duration::from_millis(50), limit.acquire(Some("b"))).await;
+		assert!(b.is_ok());
+		let a = tokio::time::timeout(Duration::from_millis(50), limit.acquire(Some("a"))).await;
+		assert!(a.is_err());
+	}
+
+	#[tokio::test]
+	async fn test_limit_is_global_across_keys() {
+		let limit = ConcurrencyLimit::new(Some(1), Some(1));
+		let _a = limit.acquire(Some("a")).await;
+		let b = tokio::time::timeout(Duration::from_millis(50), limit.acquire(Some("b"))).await;
+		assert!(b.is_err());
+	}
+
+	#[tokio::test]
+	async fn test_unused_keys_are_removed() {
+		let limit = ConcurrencyLimit::new(None, Some(1));
+		let a = limit.acquire(Some("a")).await;
+		let b = limit.acquire(Some("b")).await;
+		assert_eq!(limit.keys.lock().unwrap().len(), 2);
+		drop(a);
+		drop(b);
+		assert!(limit.keys.lock().unwrap().is_empty());
+	}
+
+	#[tokio::test]
+	async fn test_rate_limit_is_per_key() {
+		let limit = RateLimit::new(1, Duration::from_secs(60));
+		limit.acquire(Some("a")).await;
+		let b = tokio::time::timeout(Duration::from_millis(50), limit.acquire(Some("b"))).await;
+		assert!(b.is_ok());
+		let a = tokio::time::timeout(Duration::from_millis(50), limit.acquire(Some("a"))).await;
+		assert!(a.is_err());
+	}
+
+	#[tokio::test]
+	async fn test_rate_limit_waits_for_the_window() {
+		let limit = RateLimit::new(2, Duration::from_millis(100));
+		let started = Instant::now();
+		for _ in 0..3 {
+			limit.acquire(None).await;
+		}
+		assert!(started.elapsed() >= Duration::from_millis(100));
+		assert_eq!(limit.sent.lock().unwrap().len(), 1);
+	}
+}
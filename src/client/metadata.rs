@@ -0,0 +1,167 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+use reqwest::header::HeaderMap;
+
+/// Information about a single response from the API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResponseMetadata {
+	/// The HTTP status code of the response.
+	pub status: u16,
+	/// The number of requests left in the current rate limit window, from the `X-RateLimit-Remaining` header.
+	pub rate_limit_remaining: Option<u64>,
+	/// When the current rate limit window resets, as sent in the `X-RateLimit-Reset` header.
+	pub rate_limit_reset: Option<String>,
+	/// How long the API asked to wait before sending another request, from the `Retry-After` header.
+	pub retry_after: Option<Duration>,
+	/// The time between sending the request and receiving the response headers.
+	pub latency: Duration,
+	/// Whether the response was served from a cache in front of the API.
+	pub cache_hit: bool,
+	/// The size of the response body in bytes.
+	pub bytes: u64,
+	/// Whether the response was shared with an identical request that was already in flight.
+	pub shared: bool,
+}
+
+impl ResponseMetadata {
+	pub(crate) fn new(status: u16, headers: &HeaderMap, latency: Duration) -> ResponseMetadata {
+		let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+		let cache_hit = ["cf-cache-status", "x-cache"].iter()
+			.filter_map(|name| header(name))
+			.any(|v| v.to_ascii_uppercase().starts_with("HIT"));
+
+		ResponseMetadata {
+			status,
+			rate_limit_remaining: header("x-ratelimit-remaining").and_then(|v| v.parse().ok()),
+			rate_limit_reset: header("x-ratelimit-reset").map(String::from),
+			retry_after: header("retry-after").and_then(|v| v.trim().parse().ok()).map(Duration::from_secs),
+			latency,
+			cache_hit,
+			bytes: 0,
+			shared: false,
+		}
+	}
+}
+
+/// A builder that also returns the [`ResponseMetadata`] of each response.
+///
+/// To construct a `WithMetadata`, call `with_metadata()` on any of the builders.
+#[derive(Debug, Clone)]
+pub struct WithMetadata<B> {
+	pub(crate) builder: B,
+}
+
+/// A snapshot of the requests a `Client` has sent.
+///
+/// Returned by `Client::stats`. Clones of a `Client` share their statistics.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClientStats {
+	/// The number of requests sent.
+	pub requests: u64,
+	/// The number of requests that were sent again after failing. These are included in `requests`.
+	pub retries: u64,
+	/// The number of requests that failed to send, time out or decode. A failed request
+	/// that was shared with other identical requests only counts once.
+	pub errors: u64,
+	/// The number of requests that shared the response of an identical request already in flight.
+	/// These aren't included in `requests`.
+	pub shared: u64,
+	/// The number of responses served from a cache in front of the API.
+	pub cache_hits: u64,
+	/// The total size of the response bodies received in bytes.
+	pub bytes_received: u64,
+	/// The total time spent waiting for responses.
+	pub total_latency: Duration,
+}
+
+/// The running totals behind [`ClientStats`].
+#[derive(Debug, Default)]
+pub(crate) struct StatsCounters {
+	requests: AtomicU64,
+	retries: AtomicU64,
+	errors: AtomicU64,
+	shared: AtomicU64,
+	cache_hits: AtomicU64,
+	bytes_received: AtomicU64,
+	latency_micros: AtomicU64,
+}
+
+impl StatsCounters {
+	pub(crate) fn record_request(&self) {
+		self.requests.fetch_add(1, Ordering::Relaxed);
+	}
+
+	pub(crate) fn record_retry(&self) {
+		self.retries.fetch_add(1, Ordering::Relaxed);
+	}
+
+	pub(crate) fn record_error(&self) {
+		self.errors.fetch_add(1, Ordering::Relaxed);
+	}
+
+	pub(crate) fn record_shared(&self) {
+		self.shared.fetch_add(1, Ordering::Relaxed);
+	}
+
+	pub(crate) fn record_response(&self, metadata: &ResponseMetadata) {
+		if metadata.cache_hit {
+			self.cache_hits.fetch_add(1, Ordering::Relaxed);
+		}
+		self.bytes_received.fetch_add(metadata.bytes, Ordering::Relaxed);
+		self.latency_micros.fetch_add(metadata.latency.as_micros() as u64, Ordering::Relaxed);
+	}
+
+	pub(crate) fn snapshot(&self) -> ClientStats {
+		ClientStats {
+			requests: self.requests.load(Ordering::Relaxed),
+			retries: self.retries.load(Ordering::Relaxed),
+			errors: self.errors.load(Ordering::Relaxed),
+			shared: self.shared.load(Ordering::Relaxed),
+			cache_hits: self.cache_hits.load(Ordering::Relaxed),
+			bytes_received: self.bytes_received.load(Ordering::Relaxed),
+			total_latency: Duration::from_micros(self.latency_micros.load(Ordering::Relaxed)),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use reqwest::header::HeaderValue;
+
+	use super::*;
+
+	#[test]
+	fn test_metadata_from_headers() {
+		let mut headers = HeaderMap::new();
+		headers.insert("X-RateLimit-Remaining", HeaderValue::from_static("19998"));
+		headers.insert("X-RateLimit-Reset", HeaderValue::from_static("1700000000"));
+		headers.insert("CF-Cache-Status", HeaderValue::from_static("HIT"));
+		headers.insert("Retry-After", HeaderValue::from_static("30"));
+		let metadata = ResponseMetadata::new(200, &headers, Duration::from_millis(120));
+		assert_eq!(metadata.rate_limit_remaining, Some(19998));
+		assert_eq!(metadata.rate_limit_reset, Some(String::from("1700000000")));
+		assert_eq!(metadata.retry_after, Some(Duration::from_secs(30)));
+		assert!(metadata.cache_hit);
+	}
+
+	#[test]
+	fn test_stats_snapshot() {
+		let stats = StatsCounters::default();
+		let mut metadata = ResponseMetadata::new(200, &HeaderMap::new(), Duration::from_millis(5));
+		metadata.bytes = 1024;
+		stats.record_request();
+		stats.record_response(&metadata);
+		stats.record_request();
+		stats.record_error();
+		stats.record_shared();
+
+		let snapshot = stats.snapshot();
+		assert_eq!(snapshot.requests, 2);
+		assert_eq!(snapshot.errors, 1);
+		assert_eq!(snapshot.shared, 1);
+		assert_eq!(snapshot.cache_hits, 0);
+		assert_eq!(snapshot.bytes_received, 1024);
+		assert_eq!(snapshot.total_latency, Duration::from_millis(5));
+	}
+}
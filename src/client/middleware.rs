@@ -0,0 +1,182 @@
+//! Hooks that run around every request a `Client` sends.
+//!
+//! Register a [`Middleware`] with `ClientBuilder::middleware`. Middleware runs in the order
+//! it was registered.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+
+/// A hook into every request a `Client` sends.
+///
+/// Both methods do nothing by default, so implementors only need to override the ones
+/// they use.
+pub trait Middleware: Send + Sync {
+	/// Called before a request is sent. The request can be modified, such as by adding headers.
+	fn before_request(&self, request: &mut reqwest::Request) {
+		let _ = request;
+	}
+
+	/// Called once the response headers have been received, before the body is read.
+	fn after_response(&self, response: &reqwest::Response, elapsed: Duration) {
+		let _ = (response, elapsed);
+	}
+}
+
+/// The middleware registered on a `Client`.
+#[derive(Clone, Default)]
+pub(crate) struct MiddlewareChain(Vec<Arc<dyn Middleware>>);
+
+impl MiddlewareChain {
+	pub(crate) fn push(&mut self, middleware: impl Middleware + 'static) {
+		self.0.push(Arc::new(middleware));
+	}
+
+	pub(crate) fn before_request(&self, request: &mut reqwest::Request) {
+		self.0.iter().for_each(|m| m.before_request(request));
+	}
+
+	pub(crate) fn after_response(&self, response: &reqwest::Response, elapsed: Duration) {
+		self.0.iter().for_each(|m| m.after_response(response, elapsed));
+	}
+}
+
+impl core::fmt::Debug for MiddlewareChain {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "MiddlewareChain({})", self.0.len())
+	}
+}
+
+/// Headers whose values are replaced with `"<redacted>"` when logged.
+const SENSITIVE_HEADERS: [&str; 2] = ["x-api-key", "authorization"];
+
+/// Formats `headers` for logging, redacting the API key.
+pub(crate) fn redacted_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+	headers.iter()
+		.map(|(name, value)| {
+			let value = if SENSITIVE_HEADERS.contains(&name.as_str()) {
+				String::from("<redacted>")
+			} else {
+				String::from_utf8_lossy(value.as_bytes()).to_string()
+			};
+			(name.to_string(), value)
+		})
+		.collect()
+}
+
+/// Logs each request and response, with the API key redacted.
+///
+/// Lines are written to stderr unless a writer is provided with [`RequestLogger::with_writer`].
+#[derive(Clone)]
+pub struct RequestLogger {
+	writer: Arc<dyn Fn(&str) + Send + Sync>,
+}
+
+impl Default for RequestLogger {
+	fn default() -> Self {
+		RequestLogger::with_writer(|line| eprintln!("{line}"))
+	}
+}
+
+impl RequestLogger {
+	/// Returns a `RequestLogger` that writes to stderr.
+	pub fn new() -> RequestLogger {
+		RequestLogger::default()
+	}
+
+	/// Returns a `RequestLogger` that passes each line to `writer`.
+	pub fn with_writer(writer: impl Fn(&str) + Send + Sync + 'static) -> RequestLogger {
+		RequestLogger { writer: Arc::new(writer) }
+	}
+}
+
+impl Middleware for RequestLogger {
+	fn before_request(&self, request: &mut reqwest::Request) {
+		let headers = redacted_headers(request.headers()).into_iter()
+			.map(|(name, value)| format!("{name}: {value}"))
+			.collect::<Vec<_>>()
+			.join(", ");
+		(self.writer)(&format!("--> {} {} [{headers}]", request.method(), request.url()));
+	}
+
+	fn after_response(&self, response: &reqwest::Response, elapsed: Duration) {
+		(self.writer)(&format!("<-- {} {} ({}ms)", response.status(), response.url(), elapsed.as_millis()));
+	}
+}
+
+/// Adds a set of headers to every request, replacing any with the same name.
+#[derive(Debug, Clone, Default)]
+pub struct HeaderInjector {
+	headers: HeaderMap,
+}
+
+impl HeaderInjector {
+	/// Returns a `HeaderInjector` with no headers.
+	pub fn new() -> HeaderInjector {
+		HeaderInjector::default()
+	}
+
+	/// Adds a header to inject.
+	///
+	/// # Panics
+	///
+	/// This method panics if `name` or `value` isn't a valid header name or value.
+	pub fn header(mut self, name: &str, value: &str) -> HeaderInjector {
+		let name = HeaderName::from_bytes(name.as_bytes()).expect("invalid header name");
+		let value = HeaderValue::from_str(value).expect("invalid header value");
+		self.headers.insert(name, value);
+		self
+	}
+}
+
+impl From<HeaderMap> for HeaderInjector {
+	fn from(headers: HeaderMap) -> Self {
+		HeaderInjector { headers }
+	}
+}
+
+impl Middleware for HeaderInjector {
+	fn before_request(&self, request: &mut reqwest::Request) {
+		for (name, value) in &self.headers {
+			request.headers_mut().insert(name, value.clone());
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Mutex;
+
+	use super::*;
+
+	fn request() -> reqwest::Request {
+		reqwest::Client::new()
+			.get("https://api.pokemontcg.io/v2/cards")
+			.header("X-Api-Key", "secret")
+			.build()
+			.unwrap()
+	}
+
+	#[test]
+	fn test_header_injector() {
+		let mut chain = MiddlewareChain::default();
+		chain.push(HeaderInjector::new().header("User-Agent", "deckbuilder/1.0"));
+		let mut request = request();
+		chain.before_request(&mut request);
+		assert_eq!(request.headers()["user-agent"], "deckbuilder/1.0");
+	}
+
+	#[test]
+	fn test_request_logger_redacts_key() {
+		let lines = Arc::new(Mutex::new(vec![]));
+		let sink = lines.clone();
+		let logger = RequestLogger::with_writer(move |line| sink.lock().unwrap().push(line.to_string()));
+		logger.before_request(&mut request());
+
+		let lines = lines.lock().unwrap();
+		assert_eq!(lines.len(), 1usize);
+		assert!(lines[0].contains("x-api-key: <redacted>"));
+		assert!(!lines[0].contains("secret"));
+	}
+}
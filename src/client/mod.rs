@@ -1,9 +1,30 @@
 // Modules
+mod batch;
+#[allow(clippy::module_inception)]
 mod client;
+mod coalesce;
+mod config;
+mod limit;
+mod metadata;
+mod pages;
+mod preview;
 mod response;
+mod schema;
+mod typed;
 
 // Flatten
 pub use client::{Client, ClientBuilder};
+pub use batch::BatchResult;
+pub use config::ConfigError;
+pub use metadata::{ClientStats, ResponseMetadata, WithMetadata};
+pub use pages::{DecodeReport, DecodeFailure, PartialResult, Progress};
+pub use preview::RequestPreview;
+pub use schema::{SchemaDrift, SchemaDriftKind};
+pub use typed::Typed;
+pub(crate) use batch::{dedupe_ids, id_queries};
+pub(crate) use pages::{Pager, ProgressCallback};
 pub(crate) use response::{PaginatedApiResponse, ApiResponse};
+pub(crate) use schema::unknown_value;
 
 // Public Modules
+pub mod middleware;
@@ -0,0 +1,420 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::de::DeserializeOwned;
+
+use crate::{Client, Error, Paginated, Result};
+use super::{PaginatedApiResponse, ResponseMetadata};
+
+/// Collects the items that failed to decode during a lenient request.
+#[derive(Debug, Default)]
+pub struct DecodeReport {
+	/// The items that failed to decode, in the order they were found.
+	pub failures: Vec<DecodeFailure>,
+}
+
+impl DecodeReport {
+	/// Returns `true` if every item decoded.
+	pub fn is_empty(&self) -> bool {
+		self.failures.is_empty()
+	}
+
+	/// The ids of the items that failed to decode, skipping any without an id.
+	pub fn ids(&self) -> impl Iterator<Item = &str> {
+		self.failures.iter().filter_map(|f| f.id.as_deref())
+	}
+}
+
+/// An item that failed to decode during a lenient request.
+#[derive(Debug)]
+pub struct DecodeFailure {
+	/// The id of the item, if the response included one.
+	pub id: Option<String>,
+	/// The page the item was found on.
+	pub page: u32,
+	/// Why the item failed to decode.
+	pub error: Error,
+}
+
+/// The items fetched by a paginated request before one of its pages failed.
+#[derive(Debug)]
+pub struct PartialResult<T, B> {
+	/// The items fetched before the failure.
+	pub items: Vec<T>,
+	/// Why the request failed.
+	pub error: Error,
+	/// A builder that continues the request from the page that failed.
+	pub resume: B,
+}
+
+impl<T, B> core::fmt::Display for PartialResult<T, B> {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "failed after fetching {} items: {}", self.items.len(), self.error)
+	}
+}
+
+impl<T: core::fmt::Debug, B: core::fmt::Debug> std::error::Error for PartialResult<T, B> {}
+
+impl<T, B> From<PartialResult<T, B>> for Error {
+	fn from(value: PartialResult<T, B>) -> Self {
+		value.error
+	}
+}
+
+/// A snapshot of how far a paginated request has got.
+/// 
+/// When a request resumes from a later page, the counts only cover the pages that are
+/// left to fetch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+	/// The number of pages fetched so far.
+	pub pages_done: u32,
+	/// The number of pages the request will fetch.
+	pub total_pages: u32,
+	/// The number of items fetched so far.
+	pub items_fetched: u32,
+	/// The number of items the request will fetch.
+	pub total_items: u32,
+	/// The time since the first page was requested.
+	pub elapsed: Duration,
+}
+
+/// A callback that receives the [`Progress`] of a paginated request after each page.
+#[derive(Clone)]
+pub(crate) struct ProgressCallback(Arc<dyn Fn(&Progress) + Send + Sync>);
+
+impl ProgressCallback {
+	pub(crate) fn new(callback: impl Fn(&Progress) + Send + Sync + 'static) -> ProgressCallback {
+		ProgressCallback(Arc::new(callback))
+	}
+}
+
+impl core::fmt::Debug for ProgressCallback {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "ProgressCallback")
+	}
+}
+
+/// Fetches the pages of a paginated request one at a time.
+///
+/// If the request specifies a page, only that page is fetched. Otherwise every page is
+/// fetched, starting from the page the request resumes from, or the first.
+pub(crate) struct Pager<R> {
+	client: Client,
+	request: R,
+	paginate: bool,
+	finished: bool,
+	progress: Option<ProgressCallback>,
+	started: Option<Instant>,
+	first_page: u32,
+	pages_done: u32,
+	items_fetched: u32,
+	limit: Option<u32>,
+	metadata: Vec<ResponseMetadata>,
+}
+
+impl<R: Paginated> Pager<R> {
+	pub(crate) fn new(client: Client, request: R) -> Pager<R> {
+		let paginate = request.page().is_none();
+		Pager {
+			client,
+			request,
+			paginate,
+			finished: false,
+			progress: None,
+			started: None,
+			first_page: 1,
+			pages_done: 0,
+			items_fetched: 0,
+			limit: None,
+			metadata: vec![],
+		}
+	}
+
+	/// Reports the [`Progress`] of the request to `progress` after each page.
+	pub(crate) fn on_progress(mut self, progress: Option<ProgressCallback>) -> Pager<R> {
+		self.progress = progress;
+		self
+	}
+
+	/// Stops fetching pages once `limit` items have been fetched. A limit of 0 fetches nothing.
+	pub(crate) fn limit(mut self, limit: Option<u32>) -> Pager<R> {
+		self.limit = limit;
+		self.finished |= limit == Some(0);
+		self
+	}
+
+	/// The number of items the pager can still fetch before reaching its limit.
+	pub(crate) fn remaining_limit(&self) -> Option<u32> {
+		self.limit.map(|limit| limit.saturating_sub(self.items_fetched))
+	}
+
+	/// Consumes the pager, returning the request for the next page to fetch.
+	pub(crate) fn into_request(self) -> R {
+		self.request
+	}
+
+	/// Consumes the pager, returning the metadata of each page fetched.
+	pub(crate) fn into_metadata(self) -> Vec<ResponseMetadata> {
+		self.metadata
+	}
+
+	/// Fetches the next page, returning `None` once every page has been fetched.
+	pub(crate) async fn next<T>(&mut self) -> Result<Option<Vec<T>>>
+	where
+		T: DeserializeOwned,
+	{
+		if self.finished {
+			return Ok(None);
+		}
+		let mut ret: PaginatedApiResponse<T> = self.fetch().await?;
+		self.truncate(&mut ret);
+		self.advance(&ret);
+
+		Ok(Some(ret.data.unwrap_or_default()))
+	}
+
+	/// Fetches the next page, decoding each item on its own.
+	///
+	/// Items that fail to decode are added to `report` instead of failing the whole page.
+	pub(crate) async fn next_lenient<T>(&mut self, report: &mut DecodeReport) -> Result<Option<Vec<T>>>
+	where
+		T: DeserializeOwned,
+	{
+		if self.finished {
+			return Ok(None);
+		}
+		let mut ret: PaginatedApiResponse<serde_json::Value> = self.fetch().await?;
+		self.truncate(&mut ret);
+		self.advance(&ret);
+
+		Ok(Some(self.decode_items(ret, report)))
+	}
+
+	/// Fetches the current page, failing if the client's deadline has passed.
+	async fn fetch<T>(&mut self) -> Result<PaginatedApiResponse<T>>
+	where
+		T: DeserializeOwned,
+	{
+		let started = *self.started.get_or_insert_with(Instant::now);
+		let fut = self.client.get_with_metadata(self.request.clone());
+		let (ret, metadata) = match self.client.deadline() {
+			Some(deadline) => {
+				let remaining = deadline.saturating_sub(started.elapsed());
+				if remaining.is_zero() {
+					return Err(Error::DeadlineExceededError);
+				}
+				tokio::time::timeout(remaining, fut).await.map_err(|_| Error::DeadlineExceededError)??
+			},
+			None => fut.await?,
+		};
+		self.metadata.push(metadata);
+
+		Ok(ret)
+	}
+
+	fn decode_items<T>(&self, ret: PaginatedApiResponse<serde_json::Value>, report: &mut DecodeReport) -> Vec<T>
+	where
+		T: DeserializeOwned,
+	{
+		let endpoint = self.request.endpoint();
+		let mut items = vec![];
+		for value in ret.data.unwrap_or_default() {
+			let id = value.get("id").and_then(serde_json::Value::as_str).map(String::from);
+			match self.client.decode_value(&endpoint, value) {
+				Ok(item) => items.push(item),
+				Err(error) => report.failures.push(DecodeFailure { id, page: ret.page as u32, error }),
+			}
+		}
+
+		items
+	}
+
+	/// Drops any items past the limit.
+	fn truncate<T>(&self, ret: &mut PaginatedApiResponse<T>) {
+		if let (Some(remaining), Some(data)) = (self.remaining_limit(), ret.data.as_mut()) {
+			data.truncate(remaining as usize);
+		}
+	}
+
+	fn advance<T>(&mut self, ret: &PaginatedApiResponse<T>) {
+		if self.pages_done == 0 {
+			self.first_page = ret.page.max(1) as u32;
+		}
+		self.pages_done += 1;
+		self.items_fetched += ret.data.as_ref().map_or(0, Vec::len) as u32;
+		if let Some(progress) = &self.progress {
+			(progress.0)(&self.progress(ret));
+		}
+
+		let reached_limit = self.remaining_limit() == Some(0);
+		if reached_limit || !self.paginate || ret.page_size <= 0 || ret.page * ret.page_size >= ret.total_count {
+			self.finished = true;
+		} else {
+			self.request.resume_from(ret.page as u32 + 1);
+		}
+	}
+
+	fn progress<T>(&self, ret: &PaginatedApiResponse<T>) -> Progress {
+		let (total_pages, total_items) = if self.paginate && ret.page_size > 0 {
+			let skipped = (self.first_page - 1) * ret.page_size as u32;
+			let total_items = (ret.total_count.max(0) as u32).saturating_sub(skipped);
+			let total_items = self.limit.map_or(total_items, |limit| total_items.min(limit));
+			(total_items.div_ceil(ret.page_size as u32).max(1), total_items)
+		} else {
+			(1, ret.count.max(0) as u32)
+		};
+
+		Progress {
+			pages_done: self.pages_done,
+			total_pages,
+			items_fetched: self.items_fetched,
+			total_items,
+			elapsed: self.started.map(|s| s.elapsed()).unwrap_or_default(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::borrow::Cow;
+	use std::sync::Mutex;
+
+	use super::*;
+	use crate::{Card, Requestable};
+
+	#[derive(Debug, Clone, Default)]
+	struct TestRequest {
+		page: Option<u32>,
+		resume_page: Option<u32>,
+	}
+
+	impl Requestable for TestRequest {
+		fn endpoint(&self) -> Cow<'static, str> {
+			"cards".into()
+		}
+	}
+
+	impl Paginated for TestRequest {
+		fn page(&self) -> Option<u32> {
+			self.page
+		}
+
+		fn resume_from(&mut self, page: u32) {
+			self.resume_page = Some(page);
+		}
+	}
+
+	fn response<T>(data: Vec<T>, page: i32, page_size: i32, total_count: i32) -> PaginatedApiResponse<T> {
+		PaginatedApiResponse { count: data.len() as i32, data: Some(data), page, page_size, total_count }
+	}
+
+	#[test]
+	fn test_pager_fetches_partial_last_page() {
+		let mut pager = Pager::new(Client::default(), TestRequest::default());
+		pager.advance(&response::<Card>(vec![], 1, 250, 251));
+		assert!(!pager.finished);
+		pager.advance(&response::<Card>(vec![], 2, 250, 251));
+		assert!(pager.finished);
+	}
+
+	#[test]
+	fn test_pager_stops_after_specified_page() {
+		let request = TestRequest { page: Some(3), ..Default::default() };
+		let mut pager = Pager::new(Client::default(), request);
+		pager.advance(&response::<Card>(vec![], 3, 250, 1000));
+		assert!(pager.finished);
+	}
+
+	#[test]
+	fn test_pager_resumes_from_next_page() {
+		let mut pager = Pager::new(Client::default(), TestRequest::default());
+		pager.advance(&response::<Card>(vec![], 36, 250, 17500));
+		let request = pager.into_request();
+		assert_eq!(request.page, None);
+		assert_eq!(request.resume_page, Some(37));
+
+		let mut pager = Pager::new(Client::default(), request);
+		pager.advance(&response::<Card>(vec![], 37, 250, 17500));
+		assert!(!pager.finished);
+	}
+
+	#[test]
+	fn test_pager_reports_progress() {
+		let seen = Arc::new(Mutex::new(vec![]));
+		let sink = seen.clone();
+		let callback = ProgressCallback::new(move |p: &Progress| sink.lock().unwrap().push(*p));
+		let mut pager = Pager::new(Client::default(), TestRequest::default()).on_progress(Some(callback));
+		pager.advance(&response(vec![0; 2], 1, 2, 3));
+		pager.advance(&response(vec![0; 1], 2, 2, 3));
+
+		let seen = seen.lock().unwrap();
+		assert_eq!(seen.len(), 2usize);
+		assert_eq!((seen[0].pages_done, seen[0].total_pages), (1, 2));
+		assert_eq!((seen[0].items_fetched, seen[0].total_items), (2, 3));
+		assert_eq!((seen[1].pages_done, seen[1].items_fetched), (2, 3));
+	}
+
+	#[test]
+	fn test_pager_reports_progress_when_resuming() {
+		let seen = Arc::new(Mutex::new(vec![]));
+		let sink = seen.clone();
+		let callback = ProgressCallback::new(move |p: &Progress| sink.lock().unwrap().push(*p));
+		let request = TestRequest { resume_page: Some(3), ..Default::default() };
+		let mut pager = Pager::new(Client::default(), request).on_progress(Some(callback));
+		pager.advance(&response(vec![0; 2], 3, 2, 7));
+
+		let seen = seen.lock().unwrap();
+		assert_eq!((seen[0].pages_done, seen[0].total_pages), (1, 2));
+		assert_eq!((seen[0].items_fetched, seen[0].total_items), (2, 3));
+	}
+
+	#[test]
+	fn test_pager_stops_at_limit() {
+		let mut pager = Pager::new(Client::default(), TestRequest::default()).limit(Some(3));
+		let mut ret = response(vec![0; 2], 1, 2, 10);
+		pager.truncate(&mut ret);
+		pager.advance(&ret);
+		assert!(!pager.finished);
+		assert_eq!(pager.progress(&ret).total_items, 3);
+
+		let mut ret = response(vec![0; 2], 2, 2, 10);
+		pager.truncate(&mut ret);
+		pager.advance(&ret);
+		assert_eq!(ret.data.map(|d| d.len()), Some(1usize));
+		assert!(pager.finished);
+	}
+
+	#[tokio::test]
+	async fn test_pager_with_zero_limit_fetches_nothing() -> Result<()> {
+		// The deadline fails any request that is sent.
+		let client = Client::builder().deadline(Duration::ZERO).build();
+		let mut pager = Pager::new(client, TestRequest::default()).limit(Some(0));
+		assert!(pager.next::<Card>().await?.is_none());
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_pager_fails_after_deadline() {
+		let client = Client::builder().deadline(Duration::ZERO).build();
+		let mut pager = Pager::new(client, TestRequest::default());
+		let ret = pager.next::<Card>().await;
+		assert!(matches!(ret, Err(Error::DeadlineExceededError)));
+	}
+
+	#[test]
+	fn test_pager_decodes_items_leniently() {
+		let pager = Pager::new(Client::default(), TestRequest::default());
+		let data = vec![
+			serde_json::json!({"id": "xy1-1"}),
+			serde_json::json!({"id": "xy1-2", "hp": 60}),
+			serde_json::json!({"id": "xy1-3"}),
+		];
+		let mut report = DecodeReport::default();
+		let cards: Vec<Card> = pager.decode_items(response(data, 1, 250, 3), &mut report);
+		assert_eq!(cards.len(), 2usize);
+		assert_eq!(report.ids().collect::<Vec<_>>(), vec!["xy1-2"]);
+		assert_eq!(report.failures[0].page, 1);
+	}
+}
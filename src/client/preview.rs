@@ -0,0 +1,36 @@
+use super::middleware::redacted_headers;
+
+/// The request a builder would send, with the API key redacted.
+///
+/// Returned by `to_request()` on the builders. Middleware hasn't run on the request, so
+/// any headers it would add aren't included.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestPreview {
+	/// The HTTP method.
+	pub method: String,
+	/// The full URL, including the encoded query parameters.
+	pub url: String,
+	/// The headers, with the API key replaced by `"<redacted>"`.
+	pub headers: Vec<(String, String)>,
+}
+
+impl From<&reqwest::Request> for RequestPreview {
+	fn from(value: &reqwest::Request) -> Self {
+		RequestPreview {
+			method: value.method().to_string(),
+			url: value.url().to_string(),
+			headers: redacted_headers(value.headers()),
+		}
+	}
+}
+
+impl core::fmt::Display for RequestPreview {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{} {}", self.method, self.url)?;
+		for (name, value) in &self.headers {
+			write!(f, "\n{name}: {value}")?;
+		}
+
+		Ok(())
+	}
+}
@@ -0,0 +1,258 @@
+use std::cell::RefCell;
+use std::sync::Arc;
+
+use crate::{Error, Result};
+
+/// Describes a difference between a response from the API and the models in this crate.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaDrift {
+	/// The endpoint the response came from, such as "cards" or "sets/base1".
+	pub endpoint: String,
+	/// The path to the offending value in the response, such as "data.0.tcgplayer.prices.foo".
+	pub path: String,
+	/// The kind of drift that was found.
+	pub kind: SchemaDriftKind,
+}
+
+/// The kinds of [`SchemaDrift`] that can be detected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaDriftKind {
+	/// The response contained a field that isn't present on the model.
+	UnknownField,
+	/// The response contained a value that couldn't be decoded into the model's field.
+	TypeMismatch(String),
+	/// The response contained a value that was decoded into an enum's `Unknown` variant,
+	/// such as a new `Rarity`.
+	UnknownValue(String),
+}
+
+impl core::fmt::Display for SchemaDrift {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match &self.kind {
+			SchemaDriftKind::UnknownField => write!(f, "{}: unknown field `{}`", self.endpoint, self.path),
+			SchemaDriftKind::TypeMismatch(msg) => write!(f, "{}: type mismatch at `{}`: {msg}", self.endpoint, self.path),
+			SchemaDriftKind::UnknownValue(value) => write!(f, "{}: unknown value `{value}` at `{}`", self.endpoint, self.path),
+		}
+	}
+}
+
+/// How the `Client` handles responses that don't match the models.
+#[derive(Clone, Default)]
+pub(crate) enum Strictness {
+	/// Unknown fields are ignored.
+	#[default]
+	Ignore,
+	/// Drift is passed to the callback and decoding carries on.
+	Warn(Arc<dyn Fn(&SchemaDrift) + Send + Sync>),
+	/// Drift is returned as an [`Error::SchemaDriftError`].
+	Error,
+}
+
+impl core::fmt::Debug for Strictness {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Strictness::Ignore => write!(f, "Ignore"),
+			Strictness::Warn(_) => write!(f, "Warn"),
+			Strictness::Error => write!(f, "Error"),
+		}
+	}
+}
+
+/// Decodes a response body, checking it for drift according to `strictness`.
+pub(crate) fn decode<T>(endpoint: &str, body: &[u8], strictness: &Strictness) -> Result<T>
+where
+	T: serde::de::DeserializeOwned,
+{
+	if let Strictness::Ignore = strictness {
+		return Ok(serde_json::from_slice(body)?);
+	}
+
+	let locate = |index| locate_unknown_value::<T, _>(&mut serde_json::Deserializer::from_slice(body), index);
+	decode_from(endpoint, &mut serde_json::Deserializer::from_slice(body), strictness, locate)
+}
+
+/// Decodes an already parsed value, checking it for drift according to `strictness`.
+pub(crate) fn decode_value<T>(endpoint: &str, value: serde_json::Value, strictness: &Strictness) -> Result<T>
+where
+	T: serde::de::DeserializeOwned,
+{
+	if let Strictness::Ignore = strictness {
+		return Ok(serde_json::from_value(value)?);
+	}
+
+	decode_from(endpoint, &value, strictness, |index| locate_unknown_value::<T, _>(&value, index))
+}
+
+/// Decodes from `de`, collecting drift. `locate` decodes the response again to find the path
+/// to the unknown value with the given index.
+fn decode_from<'de, T, D, F>(endpoint: &str, de: D, strictness: &Strictness, locate: F) -> Result<T>
+where
+	T: serde::de::DeserializeOwned,
+	D: serde::Deserializer<'de, Error = serde_json::Error>,
+	F: Fn(usize) -> String,
+{
+	let mut drift = vec![];
+	let mut on_ignored = |path: serde_ignored::Path| drift.push(SchemaDrift {
+		endpoint: endpoint.to_string(),
+		// Optional fields show up as "?" segments, which add nothing to the path.
+		path: path.to_string().replace(".?", ""),
+		kind: SchemaDriftKind::UnknownField,
+	});
+	let ignored = serde_ignored::Deserializer::new(de, &mut on_ignored);
+	let (ret, unknown_values) = track_unknown_values(None, || {
+		serde_path_to_error::deserialize::<_, T>(ignored)
+	});
+	drift.extend(unknown_values.into_iter().enumerate().map(|(index, value)| SchemaDrift {
+		endpoint: endpoint.to_string(),
+		path: locate(index),
+		kind: SchemaDriftKind::UnknownValue(value),
+	}));
+	let ret = ret.map_err(|e| {
+		drift.push(SchemaDrift {
+			endpoint: endpoint.to_string(),
+			path: e.path().to_string(),
+			kind: SchemaDriftKind::TypeMismatch(e.inner().to_string()),
+		});
+		e.into_inner()
+	});
+
+	match strictness {
+		Strictness::Warn(callback) => drift.iter().for_each(|d| callback(d)),
+		Strictness::Error if !drift.is_empty() => return Err(Error::SchemaDriftError(drift)),
+		_ => {}
+	}
+
+	Ok(ret?)
+}
+
+/// The unknown enum values decoded on this thread while drift is being checked.
+#[derive(Default)]
+struct UnknownValues {
+	values: Vec<String>,
+	/// The index of the unknown value to fail on, to find where it is.
+	reject: Option<usize>,
+}
+
+thread_local! {
+	static UNKNOWN_VALUES: RefCell<Option<UnknownValues>> = const { RefCell::new(None) };
+}
+
+/// Runs `decode`, returning the unknown enum values it decoded.
+fn track_unknown_values<R>(reject: Option<usize>, decode: impl FnOnce() -> R) -> (R, Vec<String>) {
+	let previous = UNKNOWN_VALUES.replace(Some(UnknownValues { values: vec![], reject }));
+	let ret = decode();
+	let tracked = UNKNOWN_VALUES.replace(previous).unwrap_or_default();
+	(ret, tracked.values)
+}
+
+/// Finds the path to the unknown value with the given index by decoding again and failing on it.
+fn locate_unknown_value<'de, T, D>(de: D, index: usize) -> String
+where
+	T: serde::de::DeserializeOwned,
+	D: serde::Deserializer<'de>,
+{
+	let (ret, _) = track_unknown_values(Some(index), || serde_path_to_error::deserialize::<_, T>(de));
+	ret.err().map(|e| e.path().to_string().replace(".?", "")).unwrap_or_default()
+}
+
+/// Called by an enum when it decodes `value` into its `Unknown` variant, so the value can
+/// be reported as drift.
+pub(crate) fn unknown_value<E: serde::de::Error>(value: &str) -> core::result::Result<(), E> {
+	UNKNOWN_VALUES.with_borrow_mut(|tracked| {
+		let Some(tracked) = tracked else {
+			return Ok(());
+		};
+		let index = tracked.values.len();
+		tracked.values.push(value.to_string());
+		match tracked.reject {
+			Some(reject) if reject == index => Err(E::custom(format!("unknown value `{value}`"))),
+			_ => Ok(()),
+		}
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use std::sync::Mutex;
+
+	use super::*;
+	use crate::client::ApiResponse;
+	use crate::card::Rarity;
+	use crate::{Card, Set};
+
+	#[test]
+	fn test_decode_ignores_unknown_fields() -> Result<()> {
+		let body = br#"{"data": {"id": "base1", "shinyNewField": 1}}"#;
+		let ret: ApiResponse<Set> = decode("sets/base1", body, &Strictness::Ignore)?;
+		assert_eq!(ret.data.unwrap().id, String::from("base1"));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_decode_strict_errors_on_unknown_fields() {
+		let body = br#"{"data": {"id": "base1", "shinyNewField": 1}}"#;
+		let ret: Result<ApiResponse<Set>> = decode("sets/base1", body, &Strictness::Error);
+		match ret {
+			Err(Error::SchemaDriftError(drift)) => {
+				assert_eq!(drift.len(), 1);
+				assert_eq!(drift[0].path, String::from("data.shinyNewField"));
+				assert_eq!(drift[0].kind, SchemaDriftKind::UnknownField);
+			},
+			_ => panic!("expected a SchemaDriftError"),
+		}
+	}
+
+	#[test]
+	fn test_decode_strict_errors_on_type_mismatch() {
+		let body = br#"{"data": {"id": "base1", "total": "102"}}"#;
+		let ret: Result<ApiResponse<Set>> = decode("sets/base1", body, &Strictness::Error);
+		match ret {
+			Err(Error::SchemaDriftError(drift)) => {
+				assert_eq!(drift.len(), 1);
+				assert_eq!(drift[0].path, String::from("data.total"));
+				assert!(matches!(drift[0].kind, SchemaDriftKind::TypeMismatch(_)));
+			},
+			_ => panic!("expected a SchemaDriftError"),
+		}
+	}
+
+	#[test]
+	fn test_decode_strict_errors_on_unknown_values() {
+		let body = r#"{"data": {"id": "sv1-1", "name": "Pikachu", "supertype": "Pokémon", "subtypes": ["Basic", "Mega Evolution"], "rarity": "Mythical", "legalities": {"standard": "Restricted"}}}"#;
+		let ret: Result<ApiResponse<Card>> = decode("cards/sv1-1", body.as_bytes(), &Strictness::Error);
+		match ret {
+			Err(Error::SchemaDriftError(drift)) => {
+				let found: Vec<_> = drift.iter().map(|d| (d.path.as_str(), &d.kind)).collect();
+				assert_eq!(found, vec![
+					("data.subtypes[1]", &SchemaDriftKind::UnknownValue(String::from("Mega Evolution"))),
+					("data.rarity", &SchemaDriftKind::UnknownValue(String::from("Mythical"))),
+					("data.legalities.standard", &SchemaDriftKind::UnknownValue(String::from("Restricted"))),
+				]);
+			},
+			_ => panic!("expected a SchemaDriftError"),
+		}
+	}
+
+	#[test]
+	fn test_decode_ignores_unknown_values() -> Result<()> {
+		let body = br#"{"data": {"id": "sv1-1", "name": "Pikachu", "rarity": "Mythical"}}"#;
+		let ret: ApiResponse<Card> = decode("cards/sv1-1", body, &Strictness::Ignore)?;
+		assert_eq!(ret.data.unwrap().rarity, Some(Rarity::Unknown(String::from("Mythical"))));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_decode_warn_calls_back_and_decodes() -> Result<()> {
+		let seen = Arc::new(Mutex::new(vec![]));
+		let sink = seen.clone();
+		let strictness = Strictness::Warn(Arc::new(move |d: &SchemaDrift| sink.lock().unwrap().push(d.clone())));
+		let body = br#"{"data": {"id": "base1", "shinyNewField": 1}}"#;
+		let ret: ApiResponse<Set> = decode("sets/base1", body, &strictness)?;
+		assert_eq!(ret.data.unwrap().id, String::from("base1"));
+		assert_eq!(seen.lock().unwrap().len(), 1);
+
+		Ok(())
+	}
+}
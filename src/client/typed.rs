@@ -0,0 +1,7 @@
+/// A builder that decodes its results into enums instead of strings.
+///
+/// To construct a `Typed`, call `typed()` on the types, subtypes, supertypes or rarities builders.
+#[derive(Debug, Clone)]
+pub struct Typed<B> {
+	pub(crate) builder: B,
+}
@@ -1,5 +1,9 @@
+use std::sync::Arc;
+
 use derive_more::From;
 
+use crate::{ConfigError, SchemaDrift};
+
 pub type Result<T> = core::result::Result<T, Error>;
 
 #[derive(Debug, From)]
@@ -7,7 +11,12 @@ pub enum Error {
 	#[from]
 	ApiResponseError(reqwest::Error),
 	#[from]
-	JSONParseError(serde_json::Error)
+	JSONParseError(serde_json::Error),
+	SchemaDriftError(Vec<SchemaDrift>),
+	SharedRequestError(Arc<Error>),
+	DeadlineExceededError,
+	TimeoutError,
+	ConfigError(ConfigError),
 }
 
 impl core::fmt::Display for Error {
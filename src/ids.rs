@@ -0,0 +1,255 @@
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::card::CardNumber;
+
+/// The id of a card, such as "swsh4-183", made of the id of its set and its number.
+/// 
+/// A `CardId` can be passed anywhere a card id is taken as a string. Ids are ordered by
+/// set id and then by [`CardNumber`], so "sv1-2" comes before "sv1-10". Because that
+/// isn't the order of the strings, a `CardId` can't be borrowed as a `str`; parse the
+/// string to look it up in a map.
+/// 
+/// # Example
+/// 
+/// ```no_run
+/// # use pokemontcgio::{CardId, Client, Result};
+/// # 
+/// # async fn run() -> Result<()> {
+/// let id: CardId = "swsh4-183".parse().unwrap();
+/// assert_eq!(id.set_id().as_str(), "swsh4");
+/// let client = Client::with_api_key("YOUR_KEY");
+/// let card = client.get_card(&id).await?;
+/// let set = client.get_set(id.set_id()).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CardId(String);
+
+/// The id of a set, such as "swsh4" or "sv3pt5".
+/// 
+/// A `SetId` can be passed anywhere a set id is taken as a string.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SetId(String);
+
+/// The error returned when a string isn't a valid [`CardId`] or [`SetId`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseIdError {
+	/// The string that couldn't be parsed.
+	pub value: String,
+}
+
+impl core::fmt::Display for ParseIdError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "`{}` isn't a valid id", self.value)
+	}
+}
+
+impl std::error::Error for ParseIdError {}
+
+impl CardId {
+	/// Returns the `CardId` for the card numbered `number` in `set`.
+	/// 
+	/// # Errors
+	/// 
+	/// This method fails with a `ParseIdError` if `number` is empty or contains whitespace or "/".
+	pub fn new(set: &SetId, number: &str) -> Result<CardId, ParseIdError> {
+		let id = format!("{}-{}", set.0, number);
+		if is_id_part(number) {
+			Ok(CardId(id))
+		} else {
+			Err(ParseIdError { value: id })
+		}
+	}
+
+	/// The id of the set the card belongs to, such as "swsh4".
+	pub fn set_id(&self) -> SetId {
+		SetId(self.split().0.to_string())
+	}
+
+	/// The number of the card in its set, such as "183".
+	pub fn number(&self) -> &str {
+		self.split().1
+	}
+
+	/// The id as a string.
+	pub fn as_str(&self) -> &str {
+		&self.0
+	}
+
+	fn split(&self) -> (&str, &str) {
+		// Checked when the id was parsed.
+		self.0.split_once('-').expect("card id without a set")
+	}
+}
+
+/// Orders card ids by set id and then by [`CardNumber`], falling back to the strings.
+/// 
+/// Ids without a "-" are treated as a set id with no number.
+pub(crate) fn cmp_card_ids(a: &str, b: &str) -> Ordering {
+	let (a_set, a_number) = a.split_once('-').unwrap_or((a, ""));
+	let (b_set, b_number) = b.split_once('-').unwrap_or((b, ""));
+	a_set.cmp(b_set)
+		.then_with(|| CardNumber::from(a_number).cmp(&CardNumber::from(b_number)))
+		.then_with(|| a.cmp(b))
+}
+
+impl Ord for CardId {
+	fn cmp(&self, other: &Self) -> Ordering {
+		cmp_card_ids(&self.0, &other.0)
+	}
+}
+
+impl PartialOrd for CardId {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl SetId {
+	/// The id as a string.
+	pub fn as_str(&self) -> &str {
+		&self.0
+	}
+}
+
+/// Whether `value` could be part of an id. Some numbers, such as Unown's, are symbols.
+fn is_id_part(value: &str) -> bool {
+	!value.is_empty() && !value.chars().any(|c| c.is_whitespace() || c == '/')
+}
+
+impl FromStr for CardId {
+	type Err = ParseIdError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s.split_once('-') {
+			Some((set, number)) if is_id_part(set) && is_id_part(number) => Ok(CardId(s.to_string())),
+			_ => Err(ParseIdError { value: s.to_string() }),
+		}
+	}
+}
+
+impl FromStr for SetId {
+	type Err = ParseIdError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		if is_id_part(s) {
+			Ok(SetId(s.to_string()))
+		} else {
+			Err(ParseIdError { value: s.to_string() })
+		}
+	}
+}
+
+macro_rules! id_impls {
+	($id:ident) => {
+		impl core::fmt::Display for $id {
+			fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+				f.write_str(&self.0)
+			}
+		}
+
+		impl AsRef<str> for $id {
+			fn as_ref(&self) -> &str {
+				&self.0
+			}
+		}
+
+		impl From<$id> for String {
+			fn from(value: $id) -> Self {
+				value.0
+			}
+		}
+
+		impl From<&$id> for String {
+			fn from(value: &$id) -> Self {
+				value.0.clone()
+			}
+		}
+
+		impl Serialize for $id {
+			fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+				serializer.serialize_str(&self.0)
+			}
+		}
+
+		impl<'de> Deserialize<'de> for $id {
+			fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+				String::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+			}
+		}
+	};
+}
+
+id_impls!(CardId);
+id_impls!(SetId);
+
+// `CardId` doesn't implement `Borrow<str>`, since it doesn't order the same way as its string.
+impl Borrow<str> for SetId {
+	fn borrow(&self) -> &str {
+		&self.0
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::BTreeMap;
+
+	use super::*;
+
+	#[test]
+	fn test_card_id() -> Result<(), ParseIdError> {
+		let id: CardId = "sv3pt5-199".parse()?;
+		assert_eq!(id.set_id().as_str(), "sv3pt5");
+		assert_eq!(id.number(), "199");
+		assert_eq!(CardId::new(&"swshp".parse()?, "SWSH001")?.to_string(), "swshp-SWSH001");
+		assert!(CardId::new(&"swshp".parse()?, "").is_err());
+		assert!(CardId::new(&"sv1".parse()?, "1/198").is_err());
+		assert!("sv3pt5".parse::<CardId>().is_err());
+		assert!("sv3pt5-".parse::<CardId>().is_err());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_card_id_order() -> Result<(), ParseIdError> {
+		let mut ids: Vec<CardId> = ["sv2-1", "sv1-10", "sv1-2", "sv1-TG1"]
+			.into_iter()
+			.map(str::parse)
+			.collect::<Result<_, _>>()?;
+		ids.sort();
+		let ids: Vec<&str> = ids.iter().map(CardId::as_str).collect();
+		assert_eq!(ids, vec!["sv1-2", "sv1-10", "sv1-TG1", "sv2-1"]);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_id_map_lookup() -> Result<(), ParseIdError> {
+		let ids = ["sv1-1", "sv1-2", "sv1-3", "sv1-10", "sv1-20", "sv1-100"];
+		let cards: BTreeMap<CardId, &str> = ids.into_iter()
+			.map(|id| Ok((id.parse()?, id)))
+			.collect::<Result<_, ParseIdError>>()?;
+		for id in ids {
+			assert_eq!(cards.get(&id.parse::<CardId>()?), Some(&id));
+		}
+
+		let sets: BTreeMap<SetId, u32> = [("sv1".parse()?, 1), ("sv10".parse()?, 10), ("sv2".parse()?, 2)].into();
+		assert_eq!(sets.get("sv10"), Some(&10));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_id_serde() -> serde_json::Result<()> {
+		let id: CardId = serde_json::from_str(r#""swsh4-183""#)?;
+		assert_eq!(serde_json::to_string(&id)?, r#""swsh4-183""#);
+		assert!(serde_json::from_str::<SetId>(r#""not an id""#).is_err());
+
+		Ok(())
+	}
+}
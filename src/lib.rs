@@ -5,6 +5,7 @@ pub mod set;
 
 mod client;
 mod error;
+mod ids;
 
 mod utils;
 
@@ -13,8 +14,9 @@ pub use client::*;
 pub use card::Card;
 pub use set::Set;
 pub use error::{Error, Result};
+pub use ids::{CardId, ParseIdError, SetId};
 
-pub(crate) use utils::Requestable;
+pub(crate) use utils::{Paginated, Requestable};
 pub(crate) use utils::futurize;
 
 // Public Modules
@@ -1,8 +1,10 @@
 use std::borrow::Cow;
 use std::future::IntoFuture;
+use std::time::Duration;
 
 use crate::{futurize, Client, Requestable, Result};
 use crate::client::ApiResponse;
+use crate::{RequestPreview, ResponseMetadata, WithMetadata};
 use crate::Set;
 
 /// A builder to construct the properties for the sets/{id} endpoint
@@ -46,6 +48,18 @@ impl GetSetBuilder {
 		GetSetBuilder { client, request: GetSet::new(id.into()) }
 	}
 
+	/// The longest to wait for each request to complete, overriding the client's timeout.
+	pub fn timeout(mut self, value: Duration) -> GetSetBuilder {
+		self.client.options_mut().timeout = Some(value);
+		self
+	}
+
+	/// The API key to send with this request, overriding the client's key.
+	pub fn api_key(mut self, key: impl Into<String>) -> GetSetBuilder {
+		self.client.options_mut().api_key = Some(key.into());
+		self
+	}
+
 	/// Specific fields to fetch with the set.
 	/// 
 	/// Always includes "id" if not added.
@@ -82,10 +96,34 @@ impl GetSetBuilder {
 		let ret: ApiResponse<Set> = self.client.get(self.request).await?;
 		Ok(ret.data)
 	}
+
+	/// The URL this request would be sent to, including the encoded query parameters.
+	pub fn url(&self) -> Result<String> {
+		Ok(self.to_request()?.url)
+	}
+
+	/// The request that would be sent, with the API key redacted.
+	pub fn to_request(&self) -> Result<RequestPreview> {
+		self.client.preview(&self.request)
+	}
+
+	/// Also returns the [`ResponseMetadata`] of the response, such as its status and rate limit.
+	pub fn with_metadata(self) -> WithMetadata<GetSetBuilder> {
+		WithMetadata { builder: self }
+	}
 }
 
 futurize!(GetSetBuilder, Option<Set>);
 
+impl WithMetadata<GetSetBuilder> {
+	async fn send(self) -> Result<(Option<Set>, ResponseMetadata)> {
+		let (ret, metadata): (ApiResponse<Set>, _) = self.builder.client.get_with_metadata(self.builder.request).await?;
+		Ok((ret.data, metadata))
+	}
+}
+
+futurize!(WithMetadata<GetSetBuilder>, (Option<Set>, ResponseMetadata));
+
 impl Client {
 	/// Convenience method to make a request to the sets/{id} endpoint.
 	pub fn get_set(&self, id: impl Into<String>) -> GetSetBuilder {
@@ -0,0 +1,169 @@
+use std::future::IntoFuture;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future;
+use tokio::sync::Semaphore;
+
+use crate::{BatchResult, Client, RequestPreview, ResponseMetadata, Result, WithMetadata};
+use crate::client::{dedupe_ids, id_queries};
+use crate::Set;
+use super::SearchSetsBuilder;
+use crate::utils::futurize;
+
+/// A builder to fetch several sets by id in as few requests as possible.
+/// 
+/// To construct a `GetSetsBuilder`, refer to the `Client` documentation.
+#[derive(Debug, Clone)]
+pub struct GetSetsBuilder {
+	client: Client,
+	ids: Vec<String>,
+	select: Option<String>,
+}
+
+impl GetSetsBuilder {
+	pub(crate) fn new(client: Client, ids: Vec<String>) -> GetSetsBuilder {
+		GetSetsBuilder { client, ids, select: None }
+	}
+
+	/// The longest to wait for each request to complete, overriding the client's timeout.
+	pub fn timeout(mut self, value: Duration) -> GetSetsBuilder {
+		self.client.options_mut().timeout = Some(value);
+		self
+	}
+
+	/// The API key to send with these requests, overriding the client's key.
+	pub fn api_key(mut self, key: impl Into<String>) -> GetSetsBuilder {
+		self.client.options_mut().api_key = Some(key.into());
+		self
+	}
+
+	/// The most of these requests to send at once. The client's `max_concurrent_requests`
+	/// still applies on top of this.
+	pub fn max_concurrent_requests(mut self, value: NonZeroUsize) -> GetSetsBuilder {
+		self.client.options_mut().limit = Some(Arc::new(Semaphore::new(value.get())));
+		self
+	}
+
+	/// Specific fields to fetch with the sets.
+	/// 
+	/// Always includes "id" if not added.
+	pub fn select(mut self, value: impl Into<String>) -> GetSetsBuilder {
+		self.select = Some(value.into());
+		self
+	}
+
+	/// Sends the requests to the sets endpoint.
+	/// 
+	/// The ids are split into queries short enough to fit in a URL, which are sent at the
+	/// same time, up to `max_concurrent_requests`. This is called when awaiting the
+	/// `GetSetsBuilder` as well.
+	/// 
+	/// # Errors
+	/// 
+	/// This method fails if any of the requests fails. Ids that don't match a set aren't
+	/// an error and are listed in [`BatchResult::missing`] instead.
+	/// 
+	/// # Example
+	/// 
+	/// ```no_run
+	/// # use pokemontcgio::{Client, Result};
+	/// # 
+	/// # async fn run() -> Result<()> {
+	/// let client = Client::with_api_key("YOUR_KEY");
+	/// let sets = client.get_sets(["swsh1", "swsh2"]).await?;
+	/// println!("Not found: {:?}", sets.missing);
+	/// # Ok(())
+	/// # }
+	/// ```
+	async fn send(self) -> Result<BatchResult<Set>> {
+		let (sets, _) = self.with_metadata().await?;
+		Ok(sets)
+	}
+
+	/// The URLs of the requests that would be sent, one for each chunk of ids.
+	pub fn urls(&self) -> Result<Vec<String>> {
+		self.searches().map(|search| search.url()).collect()
+	}
+
+	/// The requests that would be sent, one for each chunk of ids, with the API key redacted.
+	pub fn to_requests(&self) -> Result<Vec<RequestPreview>> {
+		self.searches().map(|search| search.to_request()).collect()
+	}
+
+	/// Also returns the [`ResponseMetadata`] of each response, such as its status and rate limit.
+	pub fn with_metadata(self) -> WithMetadata<GetSetsBuilder> {
+		WithMetadata { builder: self }
+	}
+
+	/// A search for each chunk of ids.
+	fn searches(&self) -> impl Iterator<Item = SearchSetsBuilder> + '_ {
+		id_queries(&self.ids).into_iter().map(|query| {
+			let search = SearchSetsBuilder::new(self.client.clone()).query(query).page_size(250);
+			match &self.select {
+				Some(select) => search.select(select),
+				None => search,
+			}
+		})
+	}
+}
+
+futurize!(GetSetsBuilder, BatchResult<Set>);
+
+impl WithMetadata<GetSetsBuilder> {
+	async fn send(self) -> Result<(BatchResult<Set>, Vec<ResponseMetadata>)> {
+		let requests = self.builder.searches().map(|search| search.with_metadata().into_future());
+		let mut sets = vec![];
+		let mut metadata = vec![];
+		for (page, meta) in future::try_join_all(requests).await? {
+			sets.extend(page.into_iter().flatten());
+			metadata.extend(meta);
+		}
+
+		Ok((BatchResult::new(&self.builder.ids, sets, |set| &set.id), metadata))
+	}
+}
+
+futurize!(WithMetadata<GetSetsBuilder>, (BatchResult<Set>, Vec<ResponseMetadata>));
+
+impl Client {
+	/// Convenience method to fetch several sets by id.
+	/// 
+	/// Duplicate ids are only fetched once.
+	pub fn get_sets(&self, ids: impl IntoIterator<Item = impl Into<String>>) -> GetSetsBuilder {
+		GetSetsBuilder::new(self.clone(), dedupe_ids(ids))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	
+	fn client() -> Client {
+		let poketcg_key = dotenv::var("POKETCGAPIKEY").unwrap();
+		Client::builder().api_key(poketcg_key).build()
+	}
+
+	#[tokio::test]
+	async fn test_get_sets() -> Result<()> {
+		let client = client();
+		let sets = client.get_sets(["swsh1", "swsh2", "not-a-set"]).await?;
+		assert!(sets.found.contains_key("swsh1"));
+		assert!(sets.found.contains_key("swsh2"));
+		assert_eq!(sets.missing, vec![String::from("not-a-set")]);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_get_sets_with_metadata() -> Result<()> {
+		let client = client();
+		let (sets, metadata) = client.get_sets(["swsh1", "swsh2"]).with_metadata().await?;
+		assert_eq!(sets.found.len(), 2);
+		assert_eq!(metadata.len(), 1);
+		assert_eq!(metadata[0].status, 200);
+
+		Ok(())
+	}
+}
@@ -1,5 +1,7 @@
 mod search_sets;
 mod get_set;
+mod get_sets;
 
-pub use search_sets::SearchSetsBuilder;
-pub use get_set::GetSetBuilder;
\ No newline at end of file
+pub use search_sets::{SearchSets, SearchSetsBuilder, LenientSearchSetsBuilder, ResumableSearchSetsBuilder};
+pub use get_set::GetSetBuilder;
+pub use get_sets::GetSetsBuilder;
\ No newline at end of file
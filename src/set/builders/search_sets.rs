@@ -1,8 +1,13 @@
 use std::borrow::Cow;
 use std::future::IntoFuture;
+use std::time::Duration;
 
-use crate::{Client, Requestable, Result};
-use crate::client::PaginatedApiResponse;
+use futures::{stream, Stream, TryStreamExt};
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Serialize};
+
+use crate::{Client, DecodeReport, Error, Paginated, PartialResult, Progress, RequestPreview, Requestable, ResponseMetadata, Result, WithMetadata};
+use crate::client::{PaginatedApiResponse, Pager, ProgressCallback};
 use crate::Set;
 use crate::utils::futurize;
 
@@ -13,15 +18,66 @@ use crate::utils::futurize;
 pub struct SearchSetsBuilder {
 	client: Client,
 	request: SearchSets,
+	progress: Option<ProgressCallback>,
+	limit: Option<u32>,
 }
 
-#[derive(Debug, Clone, Default)]
+/// The parameters of a search on the sets endpoint.
+/// 
+/// A `SearchSets` can be stored or sent elsewhere with serde and turned back into a builder
+/// with [`Client::search_sets_from`]. To get one from a builder, call
+/// [`SearchSetsBuilder::request`].
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SearchSets {
+	#[serde(skip_serializing_if = "Option::is_none")]
 	query: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
 	page: Option<u32>,
+	#[serde(skip_serializing_if = "Option::is_none")]
 	page_size: Option<u32>,
+	#[serde(skip_serializing_if = "Option::is_none")]
 	order_by: Option<String>,
+	#[serde(skip_serializing_if = "Option::is_none")]
 	select: Option<String>,
+	// Only set on the builder returned by a failed resumable search, so it isn't part of the
+	// stored form.
+	#[serde(skip)]
+	resume_page: Option<u32>,
+}
+
+impl SearchSets {
+	/// Returns a `SearchSets` for `query`.
+	/// 
+	/// For information on the syntax, go to <https://pokemontcg.guru/syntax>
+	pub fn new(query: impl Into<String>) -> SearchSets {
+		SearchSets { query: Some(query.into()), ..Default::default() }
+	}
+
+	/// The query to search with.
+	pub fn query(&self) -> Option<&str> {
+		self.query.as_deref()
+	}
+
+	/// The page to start fetching results from. If `None`, all results are fetched.
+	pub fn page(&self) -> Option<u32> {
+		self.page
+	}
+
+	/// The size of the results.
+	pub fn page_size(&self) -> Option<u32> {
+		self.page_size
+	}
+
+	/// The order of the results.
+	pub fn order_by(&self) -> Option<&str> {
+		self.order_by.as_deref()
+	}
+
+	/// The fields to fetch with the sets.
+	pub fn select(&self) -> Option<&str> {
+		self.select.as_deref()
+	}
 }
 
 impl Requestable for SearchSets {
@@ -34,7 +90,7 @@ impl Requestable for SearchSets {
 		if let Some(q) = &self.query {
 			ret.push(("q".to_string(), q.clone()));
 		}
-		if let Some(page) = &self.page {
+		if let Some(page) = self.page.or(self.resume_page) {
 			ret.push(("page".to_string(), page.to_string()));
 		}
 		if let Some(page_size) = &self.page_size {
@@ -51,9 +107,39 @@ impl Requestable for SearchSets {
 	}
 }
 
+impl Paginated for SearchSets {
+	fn page(&self) -> Option<u32> {
+		self.page
+	}
+
+	fn resume_from(&mut self, page: u32) {
+		self.resume_page = Some(page);
+	}
+}
+
 impl SearchSetsBuilder {
 	pub(crate) fn new(client: Client) -> SearchSetsBuilder {
-		SearchSetsBuilder { client, request: SearchSets::default() }
+		SearchSetsBuilder { client, request: SearchSets::default(), progress: None, limit: None }
+	}
+
+	/// The longest to wait for each request to complete, overriding the client's timeout.
+	pub fn timeout(mut self, value: Duration) -> SearchSetsBuilder {
+		self.client.options_mut().timeout = Some(value);
+		self
+	}
+
+	/// The API key to send with this request, overriding the client's key.
+	pub fn api_key(mut self, key: impl Into<String>) -> SearchSetsBuilder {
+		self.client.options_mut().api_key = Some(key.into());
+		self
+	}
+
+	/// The longest the search may take to fetch all of its pages, overriding the client's deadline.
+	/// 
+	/// Once the deadline passes, the search fails with an `Error::DeadlineExceededError`.
+	pub fn deadline(mut self, value: Duration) -> SearchSetsBuilder {
+		self.client.options_mut().deadline = Some(value);
+		self
 	}
 
 	/// Query to search with.
@@ -96,6 +182,21 @@ impl SearchSetsBuilder {
 		self
 	}
 
+	/// The most sets to fetch. Pages stop being fetched once this many sets have been collected.
+	/// 
+	/// If no page size is set, the page size is lowered to the limit so no more sets are
+	/// downloaded than needed. A limit of 0 returns `None` without sending a request.
+	pub fn limit(mut self, value: u32) -> SearchSetsBuilder {
+		self.limit = Some(value);
+		self
+	}
+
+	/// Calls `callback` with the [`Progress`] of the request after each page is fetched.
+	pub fn on_progress(mut self, callback: impl Fn(&Progress) + Send + Sync + 'static) -> SearchSetsBuilder {
+		self.progress = Some(ProgressCallback::new(callback));
+		self
+	}
+
 	/// Sends the request to the sets endpoint with the provided parameters.
 	/// 
 	/// This is called when awaiting the `SearchSetsBuilder` as well.
@@ -118,25 +219,241 @@ impl SearchSetsBuilder {
 	/// ```
 	async fn send(self) -> Result<Option<Vec<Set>>> {
 		let mut sets: Vec<Set> = vec![];
+		let mut pager = self.pager();
+		while let Some(page) = pager.next().await? {
+			sets.extend(page);
+		}
+
+		if !sets.is_empty() {
+			Ok(Some(sets))
+		} else {
+			Ok(None)
+		}
+	}
+
+	/// Streams the sets one at a time, fetching each page as the previous one runs out.
+	/// 
+	/// # Example
+	/// 
+	/// ```no_run
+	/// # use futures::TryStreamExt;
+	/// # use pokemontcgio::{Client, Result};
+	/// # 
+	/// # async fn run() -> Result<()> {
+	/// let client = Client::with_api_key("YOUR_KEY");
+	/// let mut sets = std::pin::pin!(client.search_sets().stream());
+	/// while let Some(set) = sets.try_next().await? {
+	///     println!("{}", set.id);
+	/// }
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn stream(self) -> impl Stream<Item = Result<Set>> {
+		let pager = self.pager();
+		stream::try_unfold(pager, |mut pager| async move {
+			let page: Option<Vec<Set>> = pager.next().await?;
+			Ok::<_, Error>(page.map(|page| (stream::iter(page.into_iter().map(Ok)), pager)))
+		})
+		.try_flatten()
+	}
+
+	/// Returns the number of sets matching the query without fetching them.
+	/// 
+	/// This sends a single request for one set and reads the total from the response.
+	/// 
+	/// # Example
+	/// 
+	/// ```no_run
+	/// # use pokemontcgio::{Client, Result};
+	/// # 
+	/// # async fn run() -> Result<()> {
+	/// let client = Client::with_api_key("YOUR_KEY");
+	/// let count = client.search_sets().query("series:base").count().await?;
+	/// println!("{count} results");
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub async fn count(self) -> Result<u32> {
+		let mut request = self.request;
+		request.page = Some(1);
+		request.page_size = Some(1);
+		request.select = Some(String::from("id"));
+		let ret: PaginatedApiResponse<IgnoredAny> = self.client.get(request).await?;
+		Ok(ret.total_count.max(0) as u32)
+	}
+
+	/// Returns whether any sets match the query, without fetching them.
+	pub async fn exists(self) -> Result<bool> {
+		Ok(self.count().await? > 0)
+	}
+
+	/// Returns the first set matching the query, fetching only that set.
+	pub async fn first(self) -> Result<Option<Set>> {
+		let sets = self.limit(1).send().await?;
+		Ok(sets.and_then(|sets| sets.into_iter().next()))
+	}
+
+	/// Decodes each set on its own instead of failing the whole request.
+	/// 
+	/// Sets that fail to decode are skipped and listed in the returned [`DecodeReport`].
+	/// 
+	/// # Example
+	/// 
+	/// ```no_run
+	/// # use pokemontcgio::{Client, Result};
+	/// # 
+	/// # async fn run() -> Result<()> {
+	/// let client = Client::with_api_key("YOUR_KEY");
+	/// let (sets, report) = client.search_sets().lenient().await?;
+	/// println!("Skipped sets: {:?}", report.ids().collect::<Vec<_>>());
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn lenient(self) -> LenientSearchSetsBuilder {
+		LenientSearchSetsBuilder { builder: self }
+	}
+
+	/// Keeps the sets already fetched if a later page fails.
+	/// 
+	/// On failure, the returned [`PartialResult`] holds the sets fetched so far and a
+	/// builder that continues from the page that failed.
+	/// 
+	/// # Example
+	/// 
+	/// ```no_run
+	/// # use pokemontcgio::{Client, Result};
+	/// # 
+	/// # async fn run() -> Result<()> {
+	/// let client = Client::with_api_key("YOUR_KEY");
+	/// let sets = match client.search_sets().resumable().await {
+	///     Ok(sets) => sets.unwrap_or_default(),
+	///     Err(partial) => {
+	///         let mut sets = partial.items;
+	///         sets.extend(partial.resume.await?.unwrap_or_default());
+	///         sets
+	///     }
+	/// };
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn resumable(self) -> ResumableSearchSetsBuilder {
+		ResumableSearchSetsBuilder { builder: self }
+	}
+
+	/// The parameters of the search, which can be stored and used again with
+	/// [`Client::search_sets_from`].
+	/// 
+	/// The page a resumed search continues from isn't stored, so a stored search starts
+	/// from the first page again.
+	pub fn request(&self) -> &SearchSets {
+		&self.request
+	}
+
+	/// The URL this request would be sent to, including the encoded query parameters.
+	pub fn url(&self) -> Result<String> {
+		Ok(self.to_request()?.url)
+	}
+
+	/// The request that would be sent, with the API key redacted.
+	pub fn to_request(&self) -> Result<RequestPreview> {
+		self.client.preview(&self.first_request())
+	}
+
+	/// Also returns the [`ResponseMetadata`] of each page's response, such as its status and rate limit.
+	pub fn with_metadata(self) -> WithMetadata<SearchSetsBuilder> {
+		WithMetadata { builder: self }
+	}
+
+	/// The request for the first page, with the page size lowered to the limit if needed.
+	fn first_request(&self) -> SearchSets {
 		let mut request = self.request.clone();
-		// Get all pages if none is specified
-		if request.page.is_none() {
-			loop {
-				let ret: PaginatedApiResponse<Set> = self.client.get(request.clone()).await?;
-				sets.extend(ret.data.unwrap_or_default());
-				if ret.page >= ret.total_count / ret.page_size {
-					break;
+		if let Some(limit) = self.limit {
+			request.page_size = request.page_size.or(Some(limit.clamp(1, 250)));
+		}
+		request
+	}
+
+	fn pager(self) -> Pager<SearchSets> {
+		let request = self.first_request();
+		Pager::new(self.client, request).on_progress(self.progress).limit(self.limit)
+	}
+}
+
+futurize!(SearchSetsBuilder, Option<Vec<Set>>);
+
+/// A [`SearchSetsBuilder`] that skips sets which fail to decode.
+/// 
+/// To construct a `LenientSearchSetsBuilder`, call [`SearchSetsBuilder::lenient`].
+#[derive(Debug, Clone)]
+pub struct LenientSearchSetsBuilder {
+	builder: SearchSetsBuilder,
+}
+
+impl LenientSearchSetsBuilder {
+	async fn send(self) -> Result<(Option<Vec<Set>>, DecodeReport)> {
+		let mut sets: Vec<Set> = vec![];
+		let mut report = DecodeReport::default();
+		let mut pager = self.builder.pager();
+		while let Some(page) = pager.next_lenient(&mut report).await? {
+			sets.extend(page);
+		}
+
+		if !sets.is_empty() {
+			Ok((Some(sets), report))
+		} else {
+			Ok((None, report))
+		}
+	}
+}
+
+impl WithMetadata<SearchSetsBuilder> {
+	async fn send(self) -> Result<(Option<Vec<Set>>, Vec<ResponseMetadata>)> {
+		let mut sets: Vec<Set> = vec![];
+		let mut pager = self.builder.pager();
+		while let Some(page) = pager.next().await? {
+			sets.extend(page);
+		}
+
+		let metadata = pager.into_metadata();
+		if !sets.is_empty() {
+			Ok((Some(sets), metadata))
+		} else {
+			Ok((None, metadata))
+		}
+	}
+}
+
+futurize!(WithMetadata<SearchSetsBuilder>, (Option<Vec<Set>>, Vec<ResponseMetadata>));
+
+futurize!(LenientSearchSetsBuilder, (Option<Vec<Set>>, DecodeReport));
+
+/// A [`SearchSetsBuilder`] that keeps the sets already fetched if a later page fails.
+/// 
+/// To construct a `ResumableSearchSetsBuilder`, call [`SearchSetsBuilder::resumable`].
+#[derive(Debug, Clone)]
+pub struct ResumableSearchSetsBuilder {
+	builder: SearchSetsBuilder,
+}
+
+impl ResumableSearchSetsBuilder {
+	async fn send(self) -> core::result::Result<Option<Vec<Set>>, PartialResult<Set, SearchSetsBuilder>> {
+		let client = self.builder.client.clone();
+		let progress = self.builder.progress.clone();
+		let mut sets: Vec<Set> = vec![];
+		let mut pager = self.builder.pager();
+		loop {
+			match pager.next().await {
+				Ok(Some(page)) => sets.extend(page),
+				Ok(None) => break,
+				Err(error) => {
+					let limit = pager.remaining_limit();
+					let resume = SearchSetsBuilder { client, request: pager.into_request(), progress, limit };
+					return Err(PartialResult { items: sets, error, resume });
 				}
-				*request.page.get_or_insert(1) += 1;
 			}
-		// Otherwise fetch the specified page
-		} else {
-			let ret: PaginatedApiResponse<Set> = self.client.get(request.clone()).await?;
-			sets.extend(ret.data.unwrap_or_default());
 		}
-		
 
-		if sets.len() > 0usize {
+		if !sets.is_empty() {
 			Ok(Some(sets))
 		} else {
 			Ok(None)
@@ -144,7 +461,7 @@ impl SearchSetsBuilder {
 	}
 }
 
-futurize!(SearchSetsBuilder, Option<Vec<Set>>);
+futurize!(ResumableSearchSetsBuilder, Option<Vec<Set>>, PartialResult<Set, SearchSetsBuilder>);
 
 // Client implementations
 impl Client {
@@ -152,6 +469,30 @@ impl Client {
 	pub fn search_sets(&self) -> SearchSetsBuilder {
 		SearchSetsBuilder::new(self.clone())
 	}
+
+	/// Returns a builder for a search that was stored earlier.
+	/// 
+	/// # Example
+	/// 
+	/// ```no_run
+	/// # use pokemontcgio::{Client, Result};
+	/// # use pokemontcgio::set::SearchSets;
+	/// # 
+	/// # async fn run() -> Result<()> {
+	/// let client = Client::with_api_key("YOUR_KEY");
+	/// let saved: SearchSets = serde_json::from_str(r#"{"query": "series:base", "page_size": 10}"#)?;
+	/// let sets = client.search_sets_from(saved).await?;
+	/// # Ok(())
+	/// # }
+	/// ```
+	pub fn search_sets_from(&self, mut request: SearchSets) -> SearchSetsBuilder {
+		let select = request.select.take();
+		let builder = SearchSetsBuilder { request, ..SearchSetsBuilder::new(self.clone()) };
+		match select {
+			Some(select) => builder.select(select),
+			None => builder,
+		}
+	}
 }
 
 #[cfg(test)]
@@ -227,4 +568,43 @@ mod tests {
 
 		Ok(())
 	}
-}
\ No newline at end of file
+
+	#[tokio::test]
+	async fn test_search_sets_count() -> Result<()> {
+		let client = client();
+		let count = client.search_sets().query("series:base").count().await?;
+		assert!(count > 0);
+		assert!(client.search_sets().query("series:base").exists().await?);
+
+		Ok(())
+	}
+
+	#[tokio::test]
+	async fn test_search_sets_with_limit() -> Result<()> {
+		let client = client();
+		let searched_sets = client.search_sets().limit(3).await?;
+		assert_eq!(searched_sets.map(|sets| sets.len()), Some(3usize));
+		assert!(client.search_sets().first().await?.is_some());
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_search_sets_from_stored_request() -> Result<()> {
+		let client = Client::default();
+		let request = client.search_sets().query("series:base").page_size(10).select("name").request().clone();
+		let json = serde_json::to_string(&request)?;
+		assert_eq!(json, r#"{"query":"series:base","page_size":10,"select":"name,id"}"#);
+
+		let request: SearchSets = serde_json::from_str(r#"{"query":"series:base","select":"name"}"#)?;
+		assert_eq!(request.query(), Some("series:base"));
+		let builder = client.search_sets_from(request);
+		assert_eq!(builder.request().select(), Some("name,id"));
+
+		let mut request = SearchSets::new("series:base");
+		request.resume_from(3);
+		assert_eq!(serde_json::to_string(&request)?, r#"{"query":"series:base"}"#);
+
+		Ok(())
+	}
+}
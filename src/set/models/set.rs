@@ -1,7 +1,7 @@
-use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 
 use super::SetImages;
+use crate::card::{Format, Legalities};
 
 /// Represents a printed set of cards from the pokemontcg.io REST API.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,8 +17,8 @@ pub struct Set {
 	pub printed_total: Option<i64>,
 	/// The total number of cards in the set, including secret rares, alternate art, etc.
 	pub total: Option<i64>,
-	/// The legalities of the set. If a given format is not legal, it will not appear in the hash.
-	pub legalities: Option<HashMap<String, String>>,
+	/// The [`Legalities`] of the set.
+	pub legalities: Option<Legalities>,
 	/// The code the Pokémon Trading Card Game Online uses to identify a set.
 	pub ptcgo_code: Option<String>,
 	/// The date the set was released (in the USA). Format is YYYY/MM/DD.
@@ -35,4 +35,69 @@ impl PartialEq for Set {
 	}
 }
 
-impl Eq for Set {}
\ No newline at end of file
+impl Eq for Set {}
+
+impl std::hash::Hash for Set {
+	fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+		self.id.hash(state);
+	}
+}
+
+impl PartialOrd for Set {
+	fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+		Some(self.cmp(other))
+	}
+}
+
+impl Ord for Set {
+	fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+		self.id.cmp(&other.id)
+	}
+}
+
+impl Set {
+	/// Whether the set is legal in `format`.
+	pub fn is_legal_in(&self, format: Format) -> bool {
+		self.legalities.as_ref().is_some_and(|legalities| legalities.is_legal_in(format))
+	}
+}
+
+/// Dates
+#[cfg(feature = "chrono")]
+impl Set {
+	/// The date the set was released in the USA.
+	pub fn released(&self) -> Option<chrono::NaiveDate> {
+		self.release_date.as_deref().and_then(crate::utils::parse_date)
+	}
+
+	/// The date and time the set was last updated.
+	pub fn updated(&self) -> Option<chrono::NaiveDateTime> {
+		self.updated_at.as_deref().and_then(crate::utils::parse_datetime)
+	}
+
+	/// Compares sets by release date, oldest first, for use with `sort_by`. Sets without a
+	/// release date come first, and sets released on the same day are ordered by id.
+	pub fn cmp_by_release(&self, other: &Set) -> std::cmp::Ordering {
+		self.released().cmp(&other.released()).then_with(|| self.id.cmp(&other.id))
+	}
+}
+
+#[cfg(all(test, feature = "chrono"))]
+mod tests {
+	use super::*;
+
+	fn set(id: &str, release_date: &str) -> Set {
+		serde_json::from_value(serde_json::json!({"id": id, "releaseDate": release_date, "updatedAt": "2022/10/10 15:12:00"})).unwrap()
+	}
+
+	#[test]
+	fn test_set_dates() {
+		let base = set("base1", "1999/01/09");
+		assert_eq!(base.released(), chrono::NaiveDate::from_ymd_opt(1999, 1, 9));
+		assert_eq!(base.updated().map(|u| u.date()), chrono::NaiveDate::from_ymd_opt(2022, 10, 10));
+
+		let mut sets = [set("sv1", "2023/03/31"), base, set("swsh1", "2020/02/07")];
+		sets.sort_by(Set::cmp_by_release);
+		assert_eq!(sets.iter().map(|s| s.id.as_str()).collect::<Vec<_>>(), vec!["base1", "swsh1", "sv1"]);
+	}
+}
@@ -1,8 +1,11 @@
 use std::borrow::Cow;
 use std::future::IntoFuture;
+use std::time::Duration;
 
 use crate::{Client, Requestable, Result};
 use crate::client::ApiResponse;
+use crate::{RequestPreview, ResponseMetadata, Typed, WithMetadata};
+use crate::card::Supertype;
 use crate::utils::futurize;
 
 /// A builder to construct the properties for the supertypes endpoint
@@ -25,7 +28,19 @@ impl Requestable for GetSupertypes {
 
 impl GetSupertypesBuilder {
 	pub(crate) fn new(client: Client) -> GetSupertypesBuilder {
-		GetSupertypesBuilder { client, request: GetSupertypes::default() }
+		GetSupertypesBuilder { client, request: GetSupertypes }
+	}
+
+	/// The longest to wait for each request to complete, overriding the client's timeout.
+	pub fn timeout(mut self, value: Duration) -> GetSupertypesBuilder {
+		self.client.options_mut().timeout = Some(value);
+		self
+	}
+
+	/// The API key to send with this request, overriding the client's key.
+	pub fn api_key(mut self, key: impl Into<String>) -> GetSupertypesBuilder {
+		self.client.options_mut().api_key = Some(key.into());
+		self
 	}
 
 	/// Sends the request to the supertypes endpoint with the provided parameters.
@@ -52,10 +67,48 @@ impl GetSupertypesBuilder {
 		let ret: ApiResponse<Vec<String>> = self.client.get(self.request).await?;
 		Ok(ret.data)
 	}
+
+	/// The URL this request would be sent to, including the encoded query parameters.
+	pub fn url(&self) -> Result<String> {
+		Ok(self.to_request()?.url)
+	}
+
+	/// The request that would be sent, with the API key redacted.
+	pub fn to_request(&self) -> Result<RequestPreview> {
+		self.client.preview(&self.request)
+	}
+
+	/// Also returns the [`ResponseMetadata`] of the response, such as its status and rate limit.
+	pub fn with_metadata(self) -> WithMetadata<GetSupertypesBuilder> {
+		WithMetadata { builder: self }
+	}
+
+	/// Returns the supertypes as [`Supertype`]s instead of strings.
+	pub fn typed(self) -> Typed<GetSupertypesBuilder> {
+		Typed { builder: self }
+	}
 }
 
 futurize!(GetSupertypesBuilder, Option<Vec<String>>);
 
+impl WithMetadata<GetSupertypesBuilder> {
+	async fn send(self) -> Result<(Option<Vec<String>>, ResponseMetadata)> {
+		let (ret, metadata): (ApiResponse<Vec<String>>, _) = self.builder.client.get_with_metadata(self.builder.request).await?;
+		Ok((ret.data, metadata))
+	}
+}
+
+futurize!(WithMetadata<GetSupertypesBuilder>, (Option<Vec<String>>, ResponseMetadata));
+
+impl Typed<GetSupertypesBuilder> {
+	async fn send(self) -> Result<Option<Vec<Supertype>>> {
+		let ret: ApiResponse<Vec<Supertype>> = self.builder.client.get(self.builder.request).await?;
+		Ok(ret.data)
+	}
+}
+
+futurize!(Typed<GetSupertypesBuilder>, Option<Vec<Supertype>>);
+
 // Client implementations
 impl Client {
 	/// Convenience method to make a request to the supertypes endpoint.
@@ -81,4 +134,13 @@ mod tests {
 
 		Ok(())
 	}
-}
\ No newline at end of file
+
+	#[tokio::test]
+	async fn test_get_supertypes_typed() -> Result<()> {
+		let client = client();
+		let supertypes = client.get_supertypes().typed().await?;
+		assert!(supertypes.is_some_and(|supertypes| supertypes.contains(&Supertype::Pokemon)));
+
+		Ok(())
+	}
+}
@@ -1,8 +1,11 @@
 use std::borrow::Cow;
 use std::future::IntoFuture;
+use std::time::Duration;
 
 use crate::{Client, Requestable, Result};
 use crate::client::ApiResponse;
+use crate::{RequestPreview, ResponseMetadata, Typed, WithMetadata};
+use crate::card::EnergyType;
 use crate::utils::futurize;
 
 /// A builder to construct the properties for the types endpoint
@@ -25,7 +28,19 @@ impl Requestable for GetTypes {
 
 impl GetTypesBuilder {
 	pub(crate) fn new(client: Client) -> GetTypesBuilder {
-		GetTypesBuilder { client, request: GetTypes::default() }
+		GetTypesBuilder { client, request: GetTypes }
+	}
+
+	/// The longest to wait for each request to complete, overriding the client's timeout.
+	pub fn timeout(mut self, value: Duration) -> GetTypesBuilder {
+		self.client.options_mut().timeout = Some(value);
+		self
+	}
+
+	/// The API key to send with this request, overriding the client's key.
+	pub fn api_key(mut self, key: impl Into<String>) -> GetTypesBuilder {
+		self.client.options_mut().api_key = Some(key.into());
+		self
 	}
 
 	/// Sends the request to the types endpoint with the provided parameters.
@@ -52,10 +67,48 @@ impl GetTypesBuilder {
 		let ret: ApiResponse<Vec<String>> = self.client.get(self.request).await?;
 		Ok(ret.data)
 	}
+
+	/// The URL this request would be sent to, including the encoded query parameters.
+	pub fn url(&self) -> Result<String> {
+		Ok(self.to_request()?.url)
+	}
+
+	/// The request that would be sent, with the API key redacted.
+	pub fn to_request(&self) -> Result<RequestPreview> {
+		self.client.preview(&self.request)
+	}
+
+	/// Also returns the [`ResponseMetadata`] of the response, such as its status and rate limit.
+	pub fn with_metadata(self) -> WithMetadata<GetTypesBuilder> {
+		WithMetadata { builder: self }
+	}
+
+	/// Returns the types as [`EnergyType`]s instead of strings.
+	pub fn typed(self) -> Typed<GetTypesBuilder> {
+		Typed { builder: self }
+	}
 }
 
 futurize!(GetTypesBuilder, Option<Vec<String>>);
 
+impl WithMetadata<GetTypesBuilder> {
+	async fn send(self) -> Result<(Option<Vec<String>>, ResponseMetadata)> {
+		let (ret, metadata): (ApiResponse<Vec<String>>, _) = self.builder.client.get_with_metadata(self.builder.request).await?;
+		Ok((ret.data, metadata))
+	}
+}
+
+futurize!(WithMetadata<GetTypesBuilder>, (Option<Vec<String>>, ResponseMetadata));
+
+impl Typed<GetTypesBuilder> {
+	async fn send(self) -> Result<Option<Vec<EnergyType>>> {
+		let ret: ApiResponse<Vec<EnergyType>> = self.builder.client.get(self.builder.request).await?;
+		Ok(ret.data)
+	}
+}
+
+futurize!(Typed<GetTypesBuilder>, Option<Vec<EnergyType>>);
+
 // Client implementations
 impl Client {
 	/// Convenience method to make a request to the types endpoint.
@@ -81,4 +134,13 @@ mod tests {
 
 		Ok(())
 	}
-}
\ No newline at end of file
+
+	#[tokio::test]
+	async fn test_get_types_typed() -> Result<()> {
+		let client = client();
+		let types = client.get_types().typed().await?;
+		assert!(types.is_some_and(|types| types.contains(&EnergyType::Fire)));
+
+		Ok(())
+	}
+}
@@ -8,6 +8,27 @@ pub trait Requestable {
 	}
 }
 
+/// A request to an endpoint that returns its results in pages.
+pub trait Paginated: Requestable + Clone {
+	/// The single page the request fetches, if one was specified.
+	fn page(&self) -> Option<u32>;
+
+	/// Sets the page that a request for every page continues from.
+	fn resume_from(&mut self, page: u32);
+}
+
+/// Parses a date in the API's "YYYY/MM/DD" format.
+#[cfg(feature = "chrono")]
+pub(crate) fn parse_date(value: &str) -> Option<chrono::NaiveDate> {
+	chrono::NaiveDate::parse_from_str(value.trim(), "%Y/%m/%d").ok()
+}
+
+/// Parses a date and time in the API's "YYYY/MM/DD HH:MM:SS" format.
+#[cfg(feature = "chrono")]
+pub(crate) fn parse_datetime(value: &str) -> Option<chrono::NaiveDateTime> {
+	chrono::NaiveDateTime::parse_from_str(value.trim(), "%Y/%m/%d %H:%M:%S").ok()
+}
+
 macro_rules! futurize {
 	($struct:ty, $out:ty) => {
 		impl IntoFuture for $struct {
@@ -15,10 +36,109 @@ macro_rules! futurize {
 			type IntoFuture = std::pin::Pin<Box<dyn std::future::Future<Output = Self::Output>>>;
 		
 			fn into_future(self) -> Self::IntoFuture {
-				Box::pin(self.send())
+				#[cfg(feature = "tracing")]
+				let span = tracing::debug_span!("send", builder = stringify!($struct));
+				let fut = self.send();
+				#[cfg(feature = "tracing")]
+				let fut = tracing::Instrument::instrument(fut, span);
+				Box::pin(fut)
+			}
+		}
+	};
+	($struct:ty, $out:ty, $err:ty) => {
+		impl IntoFuture for $struct {
+			type Output = core::result::Result<$out, $err>;
+			type IntoFuture = std::pin::Pin<Box<dyn std::future::Future<Output = Self::Output>>>;
+		
+			fn into_future(self) -> Self::IntoFuture {
+				#[cfg(feature = "tracing")]
+				let span = tracing::debug_span!("send", builder = stringify!($struct));
+				let fut = self.send();
+				#[cfg(feature = "tracing")]
+				let fut = tracing::Instrument::instrument(fut, span);
+				Box::pin(fut)
+			}
+		}
+	};
+}
+
+pub(crate) use futurize;
+
+/// Declares an enum of the strings the API sends for a field, with an `Unknown` variant
+/// holding any string that isn't listed so new values don't fail to decode.
+///
+/// Each variant is followed by the string the API sends for it and any other spellings
+/// to accept.
+macro_rules! string_enum {
+	(
+		$(#[$meta:meta])*
+		pub enum $name:ident {
+			$( $(#[$vmeta:meta])* $variant:ident => $value:literal $(| $alias:literal)* ),* $(,)?
+		}
+	) => {
+		$(#[$meta])*
+		#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+		pub enum $name {
+			$( $(#[$vmeta])* $variant, )*
+			/// A value this version of the crate doesn't know about.
+			Unknown(String),
+		}
+
+		impl $name {
+			/// The value as the API sends it.
+			pub fn as_str(&self) -> &str {
+				match self {
+					$( $name::$variant => $value, )*
+					$name::Unknown(value) => value,
+				}
+			}
+		}
+
+		impl From<&str> for $name {
+			fn from(value: &str) -> Self {
+				match value {
+					$( $value $(| $alias)* => $name::$variant, )*
+					_ => $name::Unknown(value.to_string()),
+				}
+			}
+		}
+
+		impl From<String> for $name {
+			fn from(value: String) -> Self {
+				$name::from(value.as_str())
+			}
+		}
+
+		impl std::str::FromStr for $name {
+			type Err = std::convert::Infallible;
+
+			fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
+				Ok($name::from(s))
+			}
+		}
+
+		impl core::fmt::Display for $name {
+			fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+				f.write_str(self.as_str())
+			}
+		}
+
+		impl serde::Serialize for $name {
+			fn serialize<S: serde::Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+				serializer.serialize_str(self.as_str())
+			}
+		}
+
+		impl<'de> serde::Deserialize<'de> for $name {
+			fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+				let value = $name::from(String::deserialize(deserializer)?);
+				if let $name::Unknown(value) = &value {
+					crate::client::unknown_value(value)?;
+				}
+				Ok(value)
 			}
 		}
 	};
 }
 
-pub(crate) use futurize;
\ No newline at end of file
+pub(crate) use string_enum;